@@ -0,0 +1,207 @@
+//! Ownership audits against an expected-owners policy.
+//!
+//! Ownership of a large set of crates drifts as people join and leave: the
+//! org team gets left off a new crate, or a former maintainer keeps publish
+//! rights. An [`ExpectedOwners`] policy, usually kept in a TOML file next to
+//! the crates it governs, says who should own them:
+//!
+//! ```toml
+//! # Owners every crate must have
+//! required = ["github:acme:crates-io"]
+//! # Owners that are fine to have
+//! allowed = ["alice", "bob"]
+//! # Former maintainers who should no longer own anything
+//! departed = ["carol"]
+//! ```
+//!
+//! [`ExpectedOwners::audit`] compares a crate's actual owners (from
+//! [`crate_owners`](crate::check::crate_owners)) against the policy.
+//! Logins are compared case-insensitively, as on GitHub.
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::check::Owner;
+
+/// Who should and should not own a set of crates.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ExpectedOwners {
+    /// Logins that must own every crate, typically the org team.
+    pub required: Vec<String>,
+    /// Logins that may own crates without being reported.
+    pub allowed: Vec<String>,
+    /// Logins of people who have left and must not own any crate.
+    pub departed: Vec<String>,
+}
+
+impl FromStr for ExpectedOwners {
+    type Err = toml::de::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        toml::from_str(s)
+    }
+}
+
+/// How a crate's owners differ from the policy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+pub enum DriftKind {
+    /// A required owner is missing.
+    Missing,
+    /// A departed owner still owns the crate.
+    Departed,
+    /// An owner is neither required, allowed nor departed.
+    Unexpected,
+}
+
+impl fmt::Display for DriftKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Missing => write!(f, "missing"),
+            Self::Departed => write!(f, "departed"),
+            Self::Unexpected => write!(f, "unexpected"),
+        }
+    }
+}
+
+/// A single difference between a crate's owners and the policy.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+#[non_exhaustive]
+pub struct Drift {
+    /// What kind of difference this is.
+    pub kind: DriftKind,
+    /// The login concerned.
+    pub login: String,
+}
+
+impl fmt::Display for Drift {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.kind, self.login)
+    }
+}
+
+impl ExpectedOwners {
+    /// Compare a crate's owners against the policy.
+    ///
+    /// Missing required owners are listed first, in policy order, followed by
+    /// departed and unexpected owners in the order crates.io lists them. An
+    /// empty result means the crate is owned as expected.
+    ///
+    /// ```
+    /// use cargo_avail::audit::{DriftKind, ExpectedOwners};
+    ///
+    /// let policy: ExpectedOwners = r#"
+    ///     required = ["github:acme:owners"]
+    ///     departed = ["carol"]
+    /// "#
+    /// .parse()
+    /// .unwrap();
+    /// assert!(policy.audit(&[]).iter().any(|d| d.kind == DriftKind::Missing));
+    /// ```
+    #[must_use]
+    pub fn audit(&self, owners: &[Owner]) -> Vec<Drift> {
+        let contains =
+            |list: &[String], login: &str| list.iter().any(|l| l.eq_ignore_ascii_case(login));
+
+        let missing = self
+            .required
+            .iter()
+            .filter(|required| {
+                !owners
+                    .iter()
+                    .any(|o| o.login.eq_ignore_ascii_case(required))
+            })
+            .map(|login| Drift {
+                kind: DriftKind::Missing,
+                login: login.clone(),
+            });
+        let present = owners.iter().filter_map(|owner| {
+            let kind = if contains(&self.departed, &owner.login) {
+                DriftKind::Departed
+            } else if contains(&self.required, &owner.login)
+                || contains(&self.allowed, &owner.login)
+            {
+                return None;
+            } else {
+                DriftKind::Unexpected
+            };
+            Some(Drift {
+                kind,
+                login: owner.login.clone(),
+            })
+        });
+        missing.chain(present).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy() -> ExpectedOwners {
+        r#"
+            required = ["github:acme:owners"]
+            allowed = ["alice", "bob"]
+            departed = ["carol"]
+        "#
+        .parse()
+        .unwrap()
+    }
+
+    fn owners(json: &str) -> Vec<Owner> {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn expected_owners_pass() {
+        let owners = owners(
+            r#"[{"login":"alice","kind":"user"},{"login":"github:acme:owners","kind":"team"}]"#,
+        );
+        assert!(policy().audit(&owners).is_empty());
+    }
+
+    #[test]
+    fn missing_team_is_reported() {
+        let owners = owners(r#"[{"login":"alice","kind":"user"}]"#);
+        assert_eq!(
+            policy().audit(&owners),
+            [Drift {
+                kind: DriftKind::Missing,
+                login: "github:acme:owners".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn departed_and_unexpected_owners_are_reported() {
+        let owners = owners(
+            r#"[
+                {"login":"github:acme:owners","kind":"team"},
+                {"login":"Carol","kind":"user"},
+                {"login":"mallory","kind":"user"}
+            ]"#,
+        );
+        let drift: Vec<String> = policy()
+            .audit(&owners)
+            .iter()
+            .map(Drift::to_string)
+            .collect();
+        assert_eq!(drift, ["departed Carol", "unexpected mallory"]);
+    }
+
+    #[test]
+    fn empty_policy_flags_every_owner() {
+        let owners = owners(r#"[{"login":"alice","kind":"user"}]"#);
+        let drift = ExpectedOwners::default().audit(&owners);
+        assert_eq!(drift[0].kind, DriftKind::Unexpected);
+    }
+
+    #[test]
+    fn unknown_keys_are_rejected() {
+        assert!("requried = [\"x\"]".parse::<ExpectedOwners>().is_err());
+    }
+}
@@ -0,0 +1,405 @@
+//! Binary name collisions.
+//!
+//! A crate that installs a binary named after itself can be available on
+//! crates.io and still be a poor name: `cargo install` puts the binary in
+//! `~/.cargo/bin`, where it shadows (or is shadowed by) any executable of the
+//! same name elsewhere on `PATH`. [`binary_clashes`] looks for such
+//! executables, and compares the name against bundled lists of common Unix
+//! utilities and rustup-managed tools, which may be missing on this machine
+//! but are installed on many others. [`Installs`] recognizes the binaries a
+//! crate installed itself, which are not collisions when re-checking it.
+
+use std::collections::HashMap;
+use std::ffi::OsStr;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+use serde::Deserialize;
+
+use crate::check::canon_crate_name;
+use crate::config::cargo_home;
+
+/// Tools installed by rustup, as proxies or as components.
+const RUSTUP_TOOLS: &[&str] = &[
+    "cargo",
+    "cargo-clippy",
+    "cargo-fmt",
+    "cargo-miri",
+    "clippy-driver",
+    "miri",
+    "rls",
+    "rust-analyzer",
+    "rust-gdb",
+    "rust-gdbgui",
+    "rust-lldb",
+    "rustc",
+    "rustdoc",
+    "rustfmt",
+    "rustup",
+];
+
+/// Common Unix utilities and developer tools.
+static UNIX_TOOLS: LazyLock<Vec<&'static str>> = LazyLock::new(|| {
+    include_str!("data/unix-tools.txt")
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .collect()
+});
+
+/// What a binary name collides with.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum BinaryClash {
+    /// An executable of the same name in a `PATH` directory.
+    OnPath(PathBuf),
+    /// A common Unix utility.
+    UnixTool,
+    /// A tool installed by rustup.
+    RustupTool,
+}
+
+impl BinaryClash {
+    /// A short label for the kind of collision: `path`, `unix` or `rustup`.
+    #[must_use]
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::OnPath(_) => "path",
+            Self::UnixTool => "unix",
+            Self::RustupTool => "rustup",
+        }
+    }
+}
+
+/// A binary name and what it collides with.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct BinaryWarning {
+    /// The binary name.
+    pub binary: String,
+    /// What it collides with.
+    pub clash: BinaryClash,
+}
+
+impl fmt::Display for BinaryWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let binary = &self.binary;
+        match &self.clash {
+            BinaryClash::OnPath(path) => {
+                write!(f, "binary `{binary}` collides with {}", path.display())
+            }
+            BinaryClash::UnixTool => write!(f, "binary `{binary}` is a common Unix tool"),
+            BinaryClash::RustupTool => write!(f, "binary `{binary}` is a rustup tool"),
+        }
+    }
+}
+
+/// The collisions of a binary named `binary`: every executable of that name
+/// in the directories of `path` (a `PATH`-style list, usually
+/// `std::env::var_os("PATH")`), in order. When none is found, the bundled
+/// rustup and Unix tool lists are consulted instead, so a tool missing here
+/// is still caught.
+///
+/// ```
+/// use cargo_avail::binary::{BinaryClash, binary_clashes};
+///
+/// let clashes = binary_clashes("rustfmt", None);
+/// assert_eq!(clashes[0].clash, BinaryClash::RustupTool);
+/// assert!(binary_clashes("tokenblend", None).is_empty());
+/// ```
+#[must_use]
+pub fn binary_clashes(binary: &str, path: Option<&OsStr>) -> Vec<BinaryWarning> {
+    let warning = |clash| BinaryWarning {
+        binary: binary.to_string(),
+        clash,
+    };
+    let file_name = format!("{binary}{}", std::env::consts::EXE_SUFFIX);
+    let mut warnings: Vec<BinaryWarning> = path
+        .map(std::env::split_paths)
+        .into_iter()
+        .flatten()
+        .filter(|dir| !dir.as_os_str().is_empty())
+        .map(|dir| dir.join(&file_name))
+        .filter(|candidate| is_executable(candidate))
+        .map(|candidate| warning(BinaryClash::OnPath(candidate)))
+        .collect();
+    // The same directory can be listed twice, or under two names (`/bin`
+    // linking to `/usr/bin`)
+    let mut seen = std::collections::HashSet::new();
+    warnings.retain(|w| match &w.clash {
+        BinaryClash::OnPath(path) => {
+            seen.insert(std::fs::canonicalize(path).unwrap_or_else(|_| path.clone()))
+        }
+        _ => true,
+    });
+    if warnings.is_empty() {
+        if RUSTUP_TOOLS.contains(&binary) {
+            warnings.push(warning(BinaryClash::RustupTool));
+        } else if UNIX_TOOLS.contains(&binary) {
+            warnings.push(warning(BinaryClash::UnixTool));
+        }
+    }
+    warnings
+}
+
+/// The binaries `cargo install` has put in its `bin` directory, and the
+/// package each came from, as recorded in `.crates2.json`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Installs {
+    bin_dir: PathBuf,
+    /// Canonical package names by binary file name.
+    packages: HashMap<String, String>,
+}
+
+/// `.crates2.json` (only the fields we use).
+#[derive(Deserialize)]
+struct CratesV2 {
+    installs: HashMap<String, InstallInfo>,
+}
+
+#[derive(Deserialize)]
+struct InstallInfo {
+    #[serde(default)]
+    bins: Vec<String>,
+}
+
+impl Installs {
+    /// Read the install records of `$CARGO_INSTALL_ROOT`, or `$CARGO_HOME`
+    /// if it is not set. A missing or unreadable record means nothing is
+    /// known to be installed.
+    #[must_use]
+    pub fn load() -> Self {
+        let root = std::env::var_os("CARGO_INSTALL_ROOT")
+            .map(PathBuf::from)
+            .or_else(cargo_home);
+        root.map(|root| {
+            let json = std::fs::read_to_string(root.join(".crates2.json")).unwrap_or_default();
+            Self::parse(&root, &json)
+        })
+        .unwrap_or_default()
+    }
+
+    /// Parse the `.crates2.json` of the install root `root`, whose binaries
+    /// are in `root/bin`.
+    ///
+    /// ```
+    /// use std::path::Path;
+    /// use cargo_avail::binary::Installs;
+    ///
+    /// let json = r#"{"installs": {"acme 1.0.0 (registry+https://github.com/rust-lang/crates.io-index)": {"bins": ["acmed"]}}}"#;
+    /// let installs = Installs::parse(Path::new("/home/me/.cargo"), json);
+    /// assert!(installs.installed_by(Path::new("/home/me/.cargo/bin/acmed"), "acme"));
+    /// assert!(!installs.installed_by(Path::new("/usr/bin/acmed"), "acme"));
+    /// ```
+    #[must_use]
+    pub fn parse(root: &Path, json: &str) -> Self {
+        let packages = serde_json::from_str::<CratesV2>(json)
+            .map(|crates| {
+                crates
+                    .installs
+                    .into_iter()
+                    .flat_map(|(key, info)| {
+                        // Keys read `<name> <version> (<source>)`
+                        let package = canon_crate_name(key.split(' ').next().unwrap_or_default());
+                        info.bins.into_iter().map(move |bin| {
+                            let bin = bin
+                                .strip_suffix(std::env::consts::EXE_SUFFIX)
+                                .map_or(bin.clone(), str::to_string);
+                            (bin, package.clone())
+                        })
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        Self {
+            bin_dir: root.join("bin"),
+            packages,
+        }
+    }
+
+    /// Whether the executable at `path` was installed by `cargo install` from
+    /// the package `package`.
+    #[must_use]
+    pub fn installed_by(&self, path: &Path, package: &str) -> bool {
+        let Some(file_name) = path.file_name().and_then(OsStr::to_str) else {
+            return false;
+        };
+        let bin = file_name
+            .strip_suffix(std::env::consts::EXE_SUFFIX)
+            .unwrap_or(file_name);
+        let in_bin_dir = path.parent().is_some_and(|dir| {
+            dir == self.bin_dir
+                || matches!(
+                    (std::fs::canonicalize(dir), std::fs::canonicalize(&self.bin_dir)),
+                    (Ok(dir), Ok(bin_dir)) if dir == bin_dir
+                )
+        });
+        in_bin_dir && self.packages.get(bin) == Some(&canon_crate_name(package))
+    }
+
+    /// Whether `warning` is about an executable the package `package`
+    /// installed itself, and so no collision for that package.
+    #[must_use]
+    pub fn is_own(&self, warning: &BinaryWarning, package: &str) -> bool {
+        matches!(&warning.clash, BinaryClash::OnPath(path) if self.installed_by(path, package))
+    }
+}
+
+/// The package name and binary names in a parsed `Cargo.toml`: the `name` of
+/// each `[[bin]]` target, or the package name if there are none. `None` if
+/// the manifest has no `[package]` name.
+///
+/// ```
+/// use cargo_avail::binary::manifest_binaries;
+///
+/// let manifest = "[package]\nname = \"acme\"\n\n[[bin]]\nname = \"acmed\"\n";
+/// let (package, binaries) = manifest_binaries(&manifest.parse().unwrap()).unwrap();
+/// assert_eq!(package, "acme");
+/// assert_eq!(binaries, ["acmed"]);
+/// ```
+#[must_use]
+pub fn manifest_binaries(manifest: &toml::Table) -> Option<(String, Vec<String>)> {
+    let package = manifest.get("package")?.get("name")?.as_str()?.to_string();
+    let mut binaries: Vec<String> = manifest
+        .get("bin")
+        .and_then(toml::Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|bin| bin.get("name")?.as_str().map(str::to_string))
+        .collect();
+    if binaries.is_empty() {
+        binaries.push(package.clone());
+    }
+    Some((package, binaries))
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    path.metadata()
+        .is_ok_and(|m| m.is_file() && m.permissions().mode() & 0o111 != 0)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+    path.is_file()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bundled_lists_are_consulted_without_path() {
+        let labels = |binary| {
+            binary_clashes(binary, None)
+                .iter()
+                .map(|w| w.clash.label())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(labels("clippy-driver"), ["rustup"]);
+        assert_eq!(labels("grep"), ["unix"]);
+        assert!(labels("grepper").is_empty());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn executables_on_path_are_found() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = std::env::temp_dir().join(format!(
+            "cargo-avail-test-binary-path-{}",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_dir_all(&dir);
+        let other = dir.join("other");
+        std::fs::create_dir_all(&other).unwrap();
+        let tool = dir.join("acmetool");
+        std::fs::write(&tool, "#!/bin/sh\n").unwrap();
+        std::fs::set_permissions(&tool, std::fs::Permissions::from_mode(0o755)).unwrap();
+        // Not executable, so not a collision
+        let data = other.join("acmetool");
+        std::fs::write(&data, "").unwrap();
+        std::fs::set_permissions(&data, std::fs::Permissions::from_mode(0o644)).unwrap();
+
+        let path = std::env::join_paths([&other, &dir, &dir]).unwrap();
+        assert_eq!(
+            binary_clashes("acmetool", Some(&path)),
+            [BinaryWarning {
+                binary: "acmetool".to_string(),
+                clash: BinaryClash::OnPath(tool.clone()),
+            }]
+        );
+        assert_eq!(
+            binary_clashes("acmetool", Some(&path))[0].to_string(),
+            format!("binary `acmetool` collides with {}", tool.display())
+        );
+        // Found on PATH, so the bundled lists are not needed
+        let cat = dir.join("cat");
+        std::fs::write(&cat, "").unwrap();
+        std::fs::set_permissions(&cat, std::fs::Permissions::from_mode(0o755)).unwrap();
+        let clashes = binary_clashes("cat", Some(&path));
+        assert_eq!(clashes.len(), 1);
+        assert_eq!(clashes[0].clash.label(), "path");
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn own_installs_are_recognized() {
+        let root = Path::new("/home/me/.cargo");
+        let installs = Installs::parse(
+            root,
+            r#"{"installs": {
+                "acme-cli 1.0.0 (registry+https://github.com/rust-lang/crates.io-index)": {"bins": ["acme"]},
+                "other 0.1.0 (path+file:///src/other)": {"bins": ["other"]}
+            }}"#,
+        );
+        let own = BinaryWarning {
+            binary: "acme".to_string(),
+            clash: BinaryClash::OnPath(root.join("bin/acme")),
+        };
+        assert!(installs.is_own(&own, "acme_cli"));
+        assert!(!installs.is_own(&own, "other"));
+        let elsewhere = BinaryWarning {
+            binary: "acme".to_string(),
+            clash: BinaryClash::OnPath(PathBuf::from("/usr/bin/acme")),
+        };
+        assert!(!installs.is_own(&elsewhere, "acme-cli"));
+        let unix = BinaryWarning {
+            binary: "acme".to_string(),
+            clash: BinaryClash::UnixTool,
+        };
+        assert!(!installs.is_own(&unix, "acme-cli"));
+        assert_eq!(
+            Installs::parse(root, "not json"),
+            Installs::parse(root, "{}")
+        );
+    }
+
+    #[test]
+    fn manifest_binaries_default_to_the_package_name() {
+        let manifest: toml::Table = "[package]\nname = \"acme\"\n".parse().unwrap();
+        assert_eq!(
+            manifest_binaries(&manifest),
+            Some(("acme".to_string(), vec!["acme".to_string()]))
+        );
+        let manifest: toml::Table = r#"
+            [package]
+            name = "acme"
+
+            [[bin]]
+            name = "acme-cli"
+
+            [[bin]]
+            name = "acmed"
+        "#
+        .parse()
+        .unwrap();
+        assert_eq!(
+            manifest_binaries(&manifest).unwrap().1,
+            ["acme-cli", "acmed"]
+        );
+        assert_eq!(manifest_binaries(&toml::Table::new()), None);
+    }
+}
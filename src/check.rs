@@ -2,11 +2,15 @@
 
 use std::collections::HashSet;
 use std::fmt;
-use std::sync::LazyLock;
+use std::sync::{Arc, LazyLock, OnceLock};
 use std::time::Duration;
 
+use serde::{Deserialize, Serialize};
 use ureq::Agent;
 
+use crate::index::NameIndex;
+use crate::unicode::{self, LookalikeName};
+
 // Vendored from rust-lang/crates.io crates_io_validation crate (commit 046368f4).
 // Cannot use as a dependency because it's not published on crates.io.
 // Source: crates/crates_io_validation/src/lib.rs
@@ -171,10 +175,25 @@ pub const MAX_CONCURRENT_REQUESTS: usize = 20;
 static RESERVED_SET: LazyLock<HashSet<String>> =
     LazyLock::new(|| RESERVED_NAMES.iter().map(|s| canon_crate_name(s)).collect());
 
+/// Whether crates.io reserves `name` (compared in canonical form), so it can
+/// never be published.
+///
+/// ```
+/// use cargo_avail::check::is_reserved;
+///
+/// assert!(is_reserved("std"));
+/// assert!(is_reserved("compiler-builtins"));
+/// assert!(!is_reserved("serde"));
+/// ```
+#[must_use]
+pub fn is_reserved(name: &str) -> bool {
+    RESERVED_SET.contains(&canon_crate_name(name))
+}
+
 /// The availability status of a crate name on crates.io.
 ///
-/// Returned as the success case of [`check_name`]. All three variants
-/// represent a definitive answer from the index or validation layer.
+/// Returned as the success case of [`check_name`]. All variants represent a
+/// definitive answer from the index or validation layer.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[must_use]
 #[non_exhaustive]
@@ -185,6 +204,10 @@ pub enum Availability {
     Taken,
     /// The name is reserved by crates.io (e.g., `std`, `core`, Windows device names).
     Reserved,
+    /// The name is taken by a crate owned by the authenticated user or one of
+    /// the client's owner logins (see [`Client::with_token`] and
+    /// [`Client::with_owners`]).
+    Owned,
 }
 
 impl fmt::Display for Availability {
@@ -193,10 +216,246 @@ impl fmt::Display for Availability {
             Self::Available => write!(f, "available"),
             Self::Taken => write!(f, "taken"),
             Self::Reserved => write!(f, "reserved"),
+            Self::Owned => write!(f, "owned"),
+        }
+    }
+}
+
+/// How a checked name collided with an existing crate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum CollisionKind {
+    /// The existing crate is published under exactly the checked spelling.
+    Exact,
+    /// The existing crate differs only by case or `-`/`_` separators.
+    Canonical,
+}
+
+impl fmt::Display for CollisionKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Exact => write!(f, "exact"),
+            Self::Canonical => write!(f, "canonical"),
+        }
+    }
+}
+
+/// The existing crate that a taken name collided with.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct Collision {
+    /// The existing crate's name, spelled exactly as published.
+    pub name: String,
+    /// Whether the spellings match exactly or only canonically.
+    pub kind: CollisionKind,
+}
+
+impl Collision {
+    fn new(checked: &str, published: String) -> Self {
+        let kind = if published == checked {
+            CollisionKind::Exact
+        } else {
+            CollisionKind::Canonical
+        };
+        Self {
+            name: published,
+            kind,
+        }
+    }
+}
+
+/// The full result of checking a crate name.
+///
+/// Returned as the success case of [`check_name_report`]. Carries the
+/// [`Availability`] along with what crates.io reported about the existing
+/// crate, if any.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[must_use]
+#[non_exhaustive]
+pub struct Report {
+    /// The availability status of the name.
+    pub availability: Availability,
+    /// The existing crate the name collided with, if it is taken.
+    pub collision: Option<Collision>,
+    /// Metadata about the existing crate, if it is taken.
+    pub details: Option<CrateDetails>,
+}
+
+impl Report {
+    fn new(availability: Availability) -> Self {
+        Self {
+            availability,
+            collision: None,
+            details: None,
+        }
+    }
+
+    /// Whether the name is held by a crate whose every version is yanked.
+    ///
+    /// Such a name is still [`Availability::Taken`] -- crates.io does not free
+    /// names when versions are yanked -- but nothing can depend on it.
+    #[must_use]
+    pub fn is_yanked_only(&self) -> bool {
+        self.details.as_ref().is_some_and(CrateDetails::all_yanked)
+    }
+
+    /// What qualifies the availability: the spelling a canonical collision
+    /// was published under, and whether every version is yanked.
+    #[must_use]
+    pub fn notes(&self) -> Vec<String> {
+        let mut notes = Vec::new();
+        if let Some(collision) = &self.collision {
+            if collision.kind == CollisionKind::Canonical {
+                notes.push(format!("as {}", collision.name));
+            }
+        }
+        if self.is_yanked_only() {
+            notes.push("all versions yanked".to_string());
+        }
+        notes
+    }
+}
+
+/// Renders as the availability followed by any [`Report::notes`], e.g.
+/// `taken (as serde_json)`.
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let notes = self.notes();
+        if notes.is_empty() {
+            write!(f, "{}", self.availability)
+        } else {
+            write!(f, "{} ({})", self.availability, notes.join(", "))
+        }
+    }
+}
+
+/// Metadata about an existing crate, parsed from the crates.io API response.
+///
+/// Helps tell a thriving crate from a long-abandoned placeholder.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[non_exhaustive]
+pub struct CrateDetails {
+    /// The crate's description, if it has one.
+    pub description: Option<String>,
+    /// When the crate was first published (RFC 3339).
+    pub created_at: String,
+    /// When the crate was last updated (RFC 3339).
+    pub updated_at: String,
+    /// The highest published version.
+    pub max_version: String,
+    /// All-time downloads.
+    pub downloads: u64,
+    /// Downloads in the last 90 days, if reported.
+    pub recent_downloads: Option<u64>,
+    /// The repository URL, if set.
+    pub repository: Option<String>,
+    /// The homepage URL, if set.
+    pub homepage: Option<String>,
+    /// Number of published versions, including yanked ones.
+    pub versions: usize,
+    /// Number of yanked versions (0 if the API omitted version records).
+    pub yanked_versions: usize,
+    /// Published versions, newest first (empty if the API omitted version
+    /// records). Left out of the serialized form to keep it compact.
+    #[serde(skip)]
+    pub releases: Vec<Release>,
+}
+
+/// A single published version of a crate.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[non_exhaustive]
+pub struct Release {
+    /// The version number.
+    pub num: String,
+    /// When the version was published (RFC 3339).
+    pub created_at: String,
+    /// Whether the version is yanked.
+    pub yanked: bool,
+}
+
+impl CrateDetails {
+    /// Whether every published version is yanked.
+    #[must_use]
+    pub fn all_yanked(&self) -> bool {
+        self.versions > 0 && self.yanked_versions == self.versions
+    }
+
+    fn from_response(response: &CrateResponse) -> Self {
+        let krate = &response.krate;
+        Self {
+            description: krate.description.clone(),
+            created_at: krate.created_at.clone(),
+            updated_at: krate.updated_at.clone(),
+            max_version: krate.max_version.clone(),
+            downloads: krate.downloads,
+            recent_downloads: krate.recent_downloads,
+            repository: krate.repository.clone(),
+            homepage: krate.homepage.clone(),
+            versions: response
+                .versions
+                .as_ref()
+                .map_or(krate.versions.len(), Vec::len),
+            yanked_versions: response
+                .versions
+                .iter()
+                .flatten()
+                .filter(|v| v.yanked)
+                .count(),
+            releases: response
+                .versions
+                .iter()
+                .flatten()
+                .map(|v| Release {
+                    num: v.num.clone(),
+                    created_at: v.created_at.clone(),
+                    yanked: v.yanked,
+                })
+                .collect(),
         }
     }
 }
 
+/// Response body of `GET /api/v1/crates/{name}` (only the fields we use).
+#[derive(Deserialize)]
+struct CrateResponse {
+    #[serde(rename = "crate")]
+    krate: CrateData,
+    versions: Option<Vec<VersionData>>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct CrateData {
+    name: String,
+    description: Option<String>,
+    created_at: String,
+    updated_at: String,
+    max_version: String,
+    downloads: u64,
+    recent_downloads: Option<u64>,
+    repository: Option<String>,
+    homepage: Option<String>,
+    /// Version IDs; used when the full `versions` list is not included.
+    #[serde(deserialize_with = "null_as_default")]
+    versions: Vec<u64>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct VersionData {
+    num: String,
+    created_at: String,
+    yanked: bool,
+}
+
+fn null_as_default<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: Default + Deserialize<'de>,
+{
+    Ok(Option::deserialize(deserializer)?.unwrap_or_default())
+}
+
 /// Errors that can occur when checking a crate name.
 ///
 /// Returned as the error case of [`check_name`]. Implements
@@ -207,6 +466,9 @@ impl fmt::Display for Availability {
 pub enum CheckError {
     /// The crate name is syntactically invalid per crates.io rules.
     InvalidName(validation::InvalidCrateName),
+    /// The crate name contains non-ASCII characters that look like ASCII
+    /// (homoglyphs, typographic dashes, invisible characters).
+    Lookalike(LookalikeName),
     /// A network or HTTP error prevented querying the crates.io API.
     IndexLookup(Box<ureq::Error>),
     /// An internal error (e.g., thread panic) that prevented checking.
@@ -217,6 +479,7 @@ impl fmt::Display for CheckError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Self::InvalidName(e) => write!(f, "invalid: {e}"),
+            Self::Lookalike(e) => write!(f, "invalid: {e}"),
             Self::IndexLookup(e) => write!(f, "unknown: {e}"),
             Self::Internal(msg) => write!(f, "internal error: {msg}"),
         }
@@ -227,6 +490,7 @@ impl std::error::Error for CheckError {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
         match self {
             Self::InvalidName(e) => Some(e),
+            Self::Lookalike(e) => Some(e),
             Self::IndexLookup(e) => Some(e.as_ref()),
             Self::Internal(_) => None,
         }
@@ -239,6 +503,12 @@ impl From<validation::InvalidCrateName> for CheckError {
     }
 }
 
+impl From<LookalikeName> for CheckError {
+    fn from(e: LookalikeName) -> Self {
+        Self::Lookalike(e)
+    }
+}
+
 /// An HTTP client configured for crates.io API queries.
 ///
 /// Wraps the underlying HTTP agent to insulate callers from the specific
@@ -254,6 +524,93 @@ impl From<validation::InvalidCrateName> for CheckError {
 #[derive(Debug, Clone)]
 pub struct Client {
     agent: Agent,
+    ownership: Option<Arc<Ownership>>,
+    index: Option<Arc<NameIndex>>,
+}
+
+/// Who counts as "you" when deciding whether a taken name is [`Availability::Owned`].
+#[derive(Clone, Default)]
+struct Ownership {
+    token: Option<String>,
+    logins: Vec<String>,
+    /// The token's user login, fetched once from `/api/v1/me`.
+    login: OnceLock<String>,
+}
+
+impl fmt::Debug for Ownership {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Ownership")
+            .field("token", &self.token.as_ref().map(|_| "<redacted>"))
+            .field("logins", &self.logins)
+            .field("login", &self.login.get())
+            .finish()
+    }
+}
+
+/// Response body of `GET /api/v1/me` (only the fields we use).
+#[derive(Deserialize)]
+struct MeResponse {
+    user: UserData,
+}
+
+#[derive(Deserialize)]
+struct UserData {
+    login: String,
+}
+
+/// Whether any of `owners` is one of `logins`, ignoring case as GitHub does.
+fn owned_by(owners: &[Owner], logins: &[String]) -> bool {
+    owners
+        .iter()
+        .any(|owner| logins.iter().any(|l| l.eq_ignore_ascii_case(&owner.login)))
+}
+
+/// Response body of `GET /api/v1/crates/{name}/owners`.
+#[derive(Deserialize)]
+struct OwnersResponse {
+    users: Vec<Owner>,
+}
+
+/// Response body of `GET /api/v1/crates/{name}/reverse_dependencies` (only
+/// the fields we use).
+#[derive(Deserialize)]
+struct ReverseDependenciesResponse {
+    meta: ReverseDependenciesMeta,
+}
+
+#[derive(Deserialize)]
+struct ReverseDependenciesMeta {
+    total: u64,
+}
+
+/// Whether a crate owner is an individual or a team.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+#[non_exhaustive]
+pub enum OwnerKind {
+    /// An individual crates.io user.
+    User,
+    /// A GitHub team, with a login like `github:org:team`.
+    Team,
+}
+
+impl fmt::Display for OwnerKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::User => write!(f, "user"),
+            Self::Team => write!(f, "team"),
+        }
+    }
+}
+
+/// An owner of a crate, as listed by crates.io.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[non_exhaustive]
+pub struct Owner {
+    /// The owner's login: a username, or `github:org:team` for teams.
+    pub login: String,
+    /// Whether the owner is a user or a team.
+    pub kind: OwnerKind,
 }
 
 impl Client {
@@ -273,8 +630,139 @@ impl Client {
             .build();
         Self {
             agent: Agent::new_with_config(config),
+            ownership: None,
+            index: None,
+        }
+    }
+
+    /// Answer availability from a local [`NameIndex`] instead of the
+    /// crates.io API.
+    ///
+    /// Checks are then offline and instant, but only as current as the
+    /// index, and taken names carry no [`CrateDetails`]. Ownership checks
+    /// ([`Client::with_token`], [`Client::with_owners`]) and other lookups
+    /// still query crates.io.
+    ///
+    /// ```
+    /// use cargo_avail::check::{Availability, Client, check_name};
+    /// use cargo_avail::index::NameIndex;
+    ///
+    /// let client = Client::new().with_index(NameIndex::build(["serde_json"]));
+    /// assert_eq!(check_name(&client, "serde-json").unwrap(), Availability::Taken);
+    /// assert_eq!(check_name(&client, "serde-jsonx").unwrap(), Availability::Available);
+    /// ```
+    #[must_use]
+    pub fn with_index(mut self, index: NameIndex) -> Self {
+        self.index = Some(Arc::new(index));
+        self
+    }
+
+    /// Authenticate with a crates.io API token.
+    ///
+    /// Taken names whose crate is owned by the token's user (looked up once
+    /// from `/api/v1/me`) are then reported as [`Availability::Owned`] instead
+    /// of [`Availability::Taken`]. Use
+    /// [`credentials::cargo_token`](crate::credentials::cargo_token) to find
+    /// the token cargo itself would use.
+    ///
+    /// ```no_run
+    /// use cargo_avail::check::Client;
+    /// use cargo_avail::credentials::cargo_token;
+    ///
+    /// let mut client = Client::new();
+    /// if let Some(token) = cargo_token().unwrap() {
+    ///     client = client.with_token(token);
+    /// }
+    /// ```
+    #[must_use]
+    pub fn with_token(mut self, token: impl Into<String>) -> Self {
+        self.ownership_mut().token = Some(token.into());
+        self
+    }
+
+    /// Also report taken names owned by any of these crates.io logins as
+    /// [`Availability::Owned`].
+    ///
+    /// Logins are user names (`dtolnay`) or teams (`github:serde-rs:publish`),
+    /// as [`crate_owners`] lists them, and are compared case-insensitively.
+    /// They need no token, and are how team ownership is recognized: crates.io
+    /// does not list the teams a token's user belongs to.
+    ///
+    /// ```
+    /// use cargo_avail::check::Client;
+    ///
+    /// let client = Client::new().with_owners(["my-login", "github:my-org:owners"]);
+    /// ```
+    #[must_use]
+    pub fn with_owners<I, S>(mut self, logins: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.ownership_mut()
+            .logins
+            .extend(logins.into_iter().map(Into::into));
+        self
+    }
+
+    fn ownership_mut(&mut self) -> &mut Ownership {
+        Arc::make_mut(self.ownership.get_or_insert_default())
+    }
+
+    /// Whether the token's user or one of the configured logins owns
+    /// `canonical`.
+    fn owns(&self, ownership: &Ownership, canonical: &str) -> Result<bool, CheckError> {
+        let url = format!("https://crates.io/api/v1/crates/{canonical}/owners");
+        let owners: OwnersResponse = self.get_json(&url, None)?;
+        if owned_by(&owners.users, &ownership.logins) {
+            return Ok(true);
+        }
+        match &ownership.token {
+            Some(token) => {
+                let login = self.login(ownership, token)?;
+                Ok(owned_by(&owners.users, std::slice::from_ref(login)))
+            }
+            None => Ok(false),
         }
     }
+
+    /// The login of the token's user.
+    fn login<'a>(&self, ownership: &'a Ownership, token: &str) -> Result<&'a String, CheckError> {
+        if let Some(login) = ownership.login.get() {
+            return Ok(login);
+        }
+        let me: MeResponse = self.get_json("https://crates.io/api/v1/me", Some(token))?;
+        // Another thread may have won the race; either value is the same login.
+        let _ = ownership.login.set(me.user.login);
+        Ok(ownership.login.get().expect("login was just set"))
+    }
+
+    /// Whether a URL still resolves: `Ok(true)` for a success status (after
+    /// redirects), `Ok(false)` if the server answers 404 or 410.
+    pub(crate) fn url_exists(&self, url: &str) -> Result<bool, CheckError> {
+        match self.agent.head(url).call() {
+            Ok(_) => Ok(true),
+            Err(ureq::Error::StatusCode(404 | 410)) => Ok(false),
+            Err(e) => Err(CheckError::IndexLookup(Box::new(e))),
+        }
+    }
+
+    /// GET a crates.io API endpoint and parse the JSON body, optionally
+    /// authenticated with `token`.
+    pub(crate) fn get_json<T: serde::de::DeserializeOwned>(
+        &self,
+        url: &str,
+        token: Option<&str>,
+    ) -> Result<T, CheckError> {
+        let mut request = self.agent.get(url);
+        if let Some(token) = token {
+            request = request.header("Authorization", token);
+        }
+        request
+            .call()
+            .and_then(|mut response| response.body_mut().read_json())
+            .map_err(|e| CheckError::IndexLookup(Box::new(e)))
+    }
 }
 
 impl Default for Client {
@@ -296,18 +784,115 @@ pub fn canon_crate_name(name: &str) -> String {
     name.to_lowercase().replace('-', "_")
 }
 
+/// List the user and team owners of a crate.
+///
+/// The name is validated and canonicalized first, so `serde-json` finds the
+/// owners of `serde_json`. Returns `Ok(None)` if no such crate exists.
+///
+/// # Errors
+///
+/// Returns [`CheckError::Lookalike`] or [`CheckError::InvalidName`] if the
+/// name fails validation, or [`CheckError::IndexLookup`] if the API cannot be
+/// queried.
+///
+/// # Example
+///
+/// ```no_run
+/// use cargo_avail::check::{Client, crate_owners};
+///
+/// let client = Client::new();
+/// for owner in crate_owners(&client, "serde").unwrap().unwrap_or_default() {
+///     println!("{} ({})", owner.login, owner.kind);
+/// }
+/// ```
+pub fn crate_owners(client: &Client, name: &str) -> Result<Option<Vec<Owner>>, CheckError> {
+    unicode::check_lookalikes(name)?;
+    validation::validate_crate_name(name)?;
+
+    let url = format!(
+        "https://crates.io/api/v1/crates/{}/owners",
+        canon_crate_name(name)
+    );
+    match client.agent.get(&url).call() {
+        Ok(mut response) => {
+            let body: OwnersResponse = response
+                .body_mut()
+                .read_json()
+                .map_err(|e| CheckError::IndexLookup(Box::new(e)))?;
+            Ok(Some(body.users))
+        }
+        Err(ureq::Error::StatusCode(404)) => Ok(None),
+        Err(e) => Err(CheckError::IndexLookup(Box::new(e))),
+    }
+}
+
+/// Count the crates that depend on a crate.
+///
+/// Counts the distinct crates whose latest version depends on any version of
+/// the named crate, as shown on its crates.io "Dependents" tab. The name is
+/// validated and canonicalized first. Returns `Ok(None)` if no such crate
+/// exists.
+///
+/// # Errors
+///
+/// Returns [`CheckError::Lookalike`] or [`CheckError::InvalidName`] if the
+/// name fails validation, or [`CheckError::IndexLookup`] if the API cannot be
+/// queried.
+///
+/// # Example
+///
+/// ```no_run
+/// use cargo_avail::check::{Client, reverse_dependency_count};
+///
+/// let client = Client::new();
+/// if let Some(count) = reverse_dependency_count(&client, "serde").unwrap() {
+///     println!("{count} crates depend on serde");
+/// }
+/// ```
+pub fn reverse_dependency_count(client: &Client, name: &str) -> Result<Option<u64>, CheckError> {
+    unicode::check_lookalikes(name)?;
+    validation::validate_crate_name(name)?;
+
+    // One record per page is enough: only the total in `meta` is used
+    let url = format!(
+        "https://crates.io/api/v1/crates/{}/reverse_dependencies?per_page=1",
+        canon_crate_name(name)
+    );
+    match client.agent.get(&url).call() {
+        Ok(mut response) => {
+            let body: ReverseDependenciesResponse = response
+                .body_mut()
+                .read_json()
+                .map_err(|e| CheckError::IndexLookup(Box::new(e)))?;
+            Ok(Some(body.meta.total))
+        }
+        Err(ureq::Error::StatusCode(404)) => Ok(None),
+        Err(e) => Err(CheckError::IndexLookup(Box::new(e))),
+    }
+}
+
 /// Check whether a crate name is available on crates.io.
 ///
-/// Performs three checks in order:
-/// 1. Validates the name against crates.io naming rules.
+/// Performs these checks in order:
+/// 1. Validates the name against crates.io naming rules, explaining any
+///    non-ASCII lookalike characters.
 /// 2. Checks the name against the reserved names list.
 /// 3. Queries the crates.io API, which uses the same canonical matching
-///    as `cargo publish` (hyphens and underscores are equivalent).
+///    as `cargo publish` (hyphens and underscores are equivalent), or the
+///    client's local index ([`Client::with_index`]).
+/// 4. If the client has a token ([`Client::with_token`]) or owner logins
+///    ([`Client::with_owners`]) and the name is taken, checks whether you
+///    own it.
+///
+/// Use [`check_name_report`] to also learn which existing crate a taken name
+/// collided with.
 ///
 /// # Errors
 ///
-/// Returns [`CheckError::InvalidName`] if the name fails crates.io validation,
-/// or [`CheckError::IndexLookup`] if the API cannot be queried.
+/// Returns [`CheckError::Lookalike`] if the name contains homoglyphs or
+/// invisible characters, [`CheckError::InvalidName`] if it otherwise fails
+/// crates.io validation, or [`CheckError::IndexLookup`] if the API cannot be
+/// queried.
 ///
 /// # Example
 ///
@@ -322,24 +907,83 @@ pub fn canon_crate_name(name: &str) -> String {
 /// }
 /// ```
 pub fn check_name(client: &Client, name: &str) -> Result<Availability, CheckError> {
-    // 1. Validate using vendored crates.io logic
+    check_name_report(client, name).map(|report| report.availability)
+}
+
+/// Check whether a crate name is available, reporting what it collided with.
+///
+/// Performs the same checks as [`check_name`]. When the name is taken, the
+/// crates.io API response is parsed to recover the existing crate's exact
+/// spelling, so that `serde-json` can be reported as taken by `serde_json`.
+///
+/// # Errors
+///
+/// Same as [`check_name`]. An unparseable API response is reported as
+/// [`CheckError::IndexLookup`].
+///
+/// # Example
+///
+/// ```no_run
+/// use cargo_avail::check::{Client, check_name_report};
+///
+/// let client = Client::new();
+/// let report = check_name_report(&client, "serde-json").unwrap();
+/// if let Some(collision) = report.collision {
+///     println!("{} (as {})", report.availability, collision.name);
+/// }
+/// ```
+pub fn check_name_report(client: &Client, name: &str) -> Result<Report, CheckError> {
+    // 1. Validate using vendored crates.io logic. Lookalikes are diagnosed first
+    // so that a pasted en-dash or BOM is explained instead of reported as a bare
+    // invalid character.
+    unicode::check_lookalikes(name)?;
     validation::validate_crate_name(name)?;
 
     let canonical = canon_crate_name(name);
 
     // 2. Reserved names (checked against canonical form)
-    if RESERVED_SET.contains(&canonical) {
-        return Ok(Availability::Reserved);
+    if is_reserved(&canonical) {
+        return Ok(Report::new(Availability::Reserved));
+    }
+
+    // 3. Local index lookup, when one is configured
+    if let Some(index) = &client.index {
+        let Some(published) = index.get(&canonical) else {
+            return Ok(Report::new(Availability::Available));
+        };
+        let availability = match &client.ownership {
+            Some(ownership) if client.owns(ownership, &canonical)? => Availability::Owned,
+            _ => Availability::Taken,
+        };
+        return Ok(Report {
+            collision: Some(Collision::new(name, published)),
+            ..Report::new(availability)
+        });
     }
 
-    // 3. crates.io API lookup
+    // 4. crates.io API lookup
     // The API canonicalizes the name before querying (same logic as cargo publish),
     // so one request covers ALL separator variants. No need to guess which spelling
-    // was used when the crate was published.
+    // was used when the crate was published -- the response body tells us.
     let url = format!("https://crates.io/api/v1/crates/{canonical}");
     match client.agent.get(&url).call() {
-        Ok(_) => Ok(Availability::Taken),
-        Err(ureq::Error::StatusCode(404)) => Ok(Availability::Available),
+        Ok(mut response) => {
+            let body: CrateResponse = response
+                .body_mut()
+                .read_json()
+                .map_err(|e| CheckError::IndexLookup(Box::new(e)))?;
+            // 5. Ownership: a name we already own is not "taken" from our point of view
+            let availability = match &client.ownership {
+                Some(ownership) if client.owns(ownership, &canonical)? => Availability::Owned,
+                _ => Availability::Taken,
+            };
+            Ok(Report {
+                details: Some(CrateDetails::from_response(&body)),
+                collision: Some(Collision::new(name, body.krate.name)),
+                ..Report::new(availability)
+            })
+        }
+        Err(ureq::Error::StatusCode(404)) => Ok(Report::new(Availability::Available)),
         Err(e) => Err(CheckError::IndexLookup(Box::new(e))),
     }
 }
@@ -385,6 +1029,17 @@ mod tests {
         }
     }
 
+    #[test]
+    fn lookalike_name_returns_lookalike_error() {
+        let client = Client::new();
+        match check_name(&client, "t\u{043E}kio") {
+            Err(CheckError::Lookalike(e)) => {
+                assert_eq!(e.suggestion.as_deref(), Some("tokio"));
+            }
+            other => panic!("expected Lookalike, got {other:?}"),
+        }
+    }
+
     #[test]
     fn reserved_name_returns_reserved() {
         let client = Client::new();
@@ -442,6 +1097,127 @@ mod tests {
         }
     }
 
+    #[test]
+    fn collision_kind_from_published_spelling() {
+        let body: CrateResponse =
+            serde_json::from_str(r#"{"crate":{"name":"serde_json","id":"serde_json"}}"#).unwrap();
+        let collision = Collision::new("serde-json", body.krate.name);
+        assert_eq!(collision.name, "serde_json");
+        assert_eq!(collision.kind, CollisionKind::Canonical);
+        let exact = Collision::new("serde_json", "serde_json".to_string());
+        assert_eq!(exact.kind, CollisionKind::Exact);
+    }
+
+    #[test]
+    fn details_parsed_from_api_response() {
+        let body: CrateResponse = serde_json::from_str(
+            r#"{
+                "crate": {
+                    "name": "serde_json",
+                    "description": "A JSON serialization file format",
+                    "created_at": "2015-08-07T16:51:25.658004+00:00",
+                    "updated_at": "2025-03-03T19:43:09.112390+00:00",
+                    "max_version": "1.0.140",
+                    "downloads": 450000000,
+                    "recent_downloads": 80000000,
+                    "repository": "https://github.com/serde-rs/json",
+                    "homepage": null,
+                    "versions": [1, 2, 3]
+                },
+                "versions": [{"num": "1.0.140"}, {"num": "1.0.139", "yanked": true}]
+            }"#,
+        )
+        .unwrap();
+        let details = CrateDetails::from_response(&body);
+        assert_eq!(details.max_version, "1.0.140");
+        assert_eq!(details.downloads, 450_000_000);
+        assert_eq!(details.recent_downloads, Some(80_000_000));
+        assert_eq!(details.homepage, None);
+        // The full versions list wins over the ID list
+        assert_eq!(details.versions, 2);
+        assert_eq!(details.yanked_versions, 1);
+        assert_eq!(details.releases[1].num, "1.0.139");
+        assert!(details.releases[1].yanked);
+        assert!(!details.all_yanked());
+    }
+
+    #[test]
+    fn yanked_only_detected_from_version_records() {
+        let body: CrateResponse = serde_json::from_str(
+            r#"{
+                "crate": {"name": "gone", "max_version": "0.0.0"},
+                "versions": [{"num": "0.2.0", "yanked": true}, {"num": "0.1.0", "yanked": true}]
+            }"#,
+        )
+        .unwrap();
+        let report = Report {
+            details: Some(CrateDetails::from_response(&body)),
+            ..Report::new(Availability::Taken)
+        };
+        assert!(report.is_yanked_only());
+        assert!(!Report::new(Availability::Taken).is_yanked_only());
+    }
+
+    #[test]
+    fn yanked_only_rendered_in_status() {
+        let release = |num: &str| Release {
+            num: num.to_string(),
+            created_at: "2020-01-01T00:00:00Z".to_string(),
+            yanked: true,
+        };
+        let details = CrateDetails {
+            description: None,
+            created_at: "2020-01-01T00:00:00Z".to_string(),
+            updated_at: "2020-02-01T00:00:00Z".to_string(),
+            max_version: "0.0.0".to_string(),
+            downloads: 0,
+            recent_downloads: None,
+            repository: None,
+            homepage: None,
+            versions: 2,
+            yanked_versions: 2,
+            releases: vec![release("0.2.0"), release("0.1.0")],
+        };
+        let report = Report {
+            collision: Some(Collision::new("old-thing", "Old_Thing".to_string())),
+            details: Some(details),
+            ..Report::new(Availability::Taken)
+        };
+        assert!(report.is_yanked_only());
+        assert_eq!(
+            report.to_string(),
+            "taken (as Old_Thing, all versions yanked)"
+        );
+    }
+
+    #[test]
+    fn details_tolerate_missing_fields() {
+        let body: CrateResponse =
+            serde_json::from_str(r#"{"crate":{"name":"x","versions":null}}"#).unwrap();
+        let details = CrateDetails::from_response(&body);
+        assert_eq!(details.versions, 0);
+        assert_eq!(details.description, None);
+    }
+
+    #[test]
+    fn case_difference_is_canonical_collision() {
+        let collision = Collision::new("Serde", "serde".to_string());
+        assert_eq!(collision.kind, CollisionKind::Canonical);
+    }
+
+    #[test]
+    #[ignore = "requires network access"]
+    fn canonical_collision_reports_published_name() {
+        let client = Client::new();
+        let report = check_name_report(&client, "serde-json").unwrap();
+        assert_eq!(report.availability, Availability::Taken);
+        let collision = report
+            .collision
+            .expect("taken name should report collision");
+        assert_eq!(collision.name, "serde_json");
+        assert_eq!(collision.kind, CollisionKind::Canonical);
+    }
+
     #[test]
     #[ignore = "requires network access"]
     fn canonical_collision_via_api() {
@@ -462,6 +1238,89 @@ mod tests {
         assert_normal::<Availability>();
     }
 
+    #[test]
+    fn report_is_send_sync() {
+        fn assert_normal<T: Sized + Send + Sync + Unpin>() {}
+        assert_normal::<Report>();
+    }
+
+    #[test]
+    fn owners_response_parsed() {
+        let owners: OwnersResponse = serde_json::from_str(
+            r#"{"users":[
+                {"id":1,"login":"dtolnay","kind":"user","name":"David Tolnay"},
+                {"id":2,"login":"github:serde-rs:publish","kind":"team","name":"publish"}
+            ]}"#,
+        )
+        .unwrap();
+        let logins: Vec<&str> = owners.users.iter().map(|o| o.login.as_str()).collect();
+        assert_eq!(logins, ["dtolnay", "github:serde-rs:publish"]);
+        assert_eq!(owners.users[0].kind, OwnerKind::User);
+        assert_eq!(owners.users[1].kind, OwnerKind::Team);
+    }
+
+    #[test]
+    fn reverse_dependencies_total_parsed() {
+        let body: ReverseDependenciesResponse = serde_json::from_str(
+            r#"{"dependencies":[{"id":1,"crate_id":"serde"}],"versions":[],"meta":{"total":52011}}"#,
+        )
+        .unwrap();
+        assert_eq!(body.meta.total, 52_011);
+    }
+
+    #[test]
+    fn owned_resolved_from_owners_response() {
+        let owners: OwnersResponse = serde_json::from_str(
+            r#"{"users":[
+                {"id":1,"login":"Alice","kind":"user","name":"Alice"},
+                {"id":2,"login":"github:acme:owners","kind":"team","name":"owners"}
+            ]}"#,
+        )
+        .unwrap();
+        let logins = |logins: &[&str]| logins.iter().map(ToString::to_string).collect::<Vec<_>>();
+        assert!(owned_by(&owners.users, &logins(&["alice"])));
+        assert!(owned_by(
+            &owners.users,
+            &logins(&["bob", "github:acme:owners"])
+        ));
+        assert!(!owned_by(
+            &owners.users,
+            &logins(&["bob", "github:acme:other"])
+        ));
+        assert!(!owned_by(&owners.users, &[]));
+    }
+
+    #[test]
+    fn client_debug_redacts_token() {
+        let client = Client::new()
+            .with_token("cio_secret")
+            .with_owners(["github:acme:owners"]);
+        let debug = format!("{client:?}");
+        assert!(!debug.contains("cio_secret"), "{debug}");
+        assert!(debug.contains("github:acme:owners"), "{debug}");
+    }
+
+    #[test]
+    fn me_response_gives_token_login() {
+        let me: MeResponse = serde_json::from_str(
+            r#"{"user":{"id":1,"login":"alice","name":"Alice"},"owned_crates":[]}"#,
+        )
+        .unwrap();
+        assert_eq!(me.user.login, "alice");
+    }
+
+    #[test]
+    fn owned_is_reserved_before_ownership_lookup() {
+        // Reserved names never reach the API, even with a token configured
+        let client = Client::new()
+            .with_token("cio_invalid")
+            .with_owners(["alice"]);
+        match check_name(&client, "std") {
+            Ok(Availability::Reserved) => {}
+            other => panic!("expected Reserved, got {other:?}"),
+        }
+    }
+
     #[test]
     fn check_error_is_send_sync() {
         fn assert_normal<T: Sized + Send + Sync>() {}
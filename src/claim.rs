@@ -0,0 +1,451 @@
+//! Draft crates.io name-transfer requests for dormant crates.
+//!
+//! The crates.io usage policy asks anyone who wants an existing crate name to
+//! first try to reach its owners, and only then to write to the crates.io team
+//! with the evidence that the crate is unmaintained. [`ClaimRequest::gather`]
+//! collects that evidence from the crates.io API (owners, publish history,
+//! repository link, downloads) and [`ClaimRequest::render`] turns it into a
+//! draft with placeholders for the parts only a human can fill in.
+//!
+//! Nothing here contacts the owners or the crates.io team: the draft is
+//! printed for you to review and send yourself. The repository link is only
+//! visited when you ask for it with [`ClaimRequest::check_repository`].
+
+use std::fmt::{self, Write as _};
+use std::str::FromStr;
+
+use crate::check::{
+    Availability, CheckError, Client, CrateDetails, Owner, check_name_report, crate_owners,
+};
+use crate::classify::{Classification, ClassifyOptions, Label, classify};
+
+/// Where crates.io name-transfer requests are sent.
+pub const HELP_ADDRESS: &str = "help@crates.io";
+
+const POLICY_URL: &str = "https://crates.io/policies";
+
+/// At most this many releases are listed in the publish history.
+const MAX_HISTORY: usize = 10;
+
+/// Whether a crate's repository link still leads anywhere.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum RepositoryStatus {
+    /// The crate lists no repository.
+    Missing,
+    /// The repository URL responds.
+    Reachable,
+    /// The repository URL answers 404 or 410.
+    Gone,
+    /// The repository could not be checked (not HTTP, or a network error).
+    Unknown,
+    /// The repository URL was not visited.
+    Unchecked,
+}
+
+impl fmt::Display for RepositoryStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Missing => write!(f, "none listed"),
+            Self::Reachable => write!(f, "reachable"),
+            Self::Gone => write!(f, "no longer exists"),
+            Self::Unknown => write!(f, "could not be checked"),
+            Self::Unchecked => write!(f, "status: unchecked"),
+        }
+    }
+}
+
+/// The format of a rendered draft.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum DraftFormat {
+    /// Markdown headings and code spans.
+    #[default]
+    Markdown,
+    /// Plain text, for mail clients that do not render Markdown.
+    Text,
+}
+
+impl FromStr for DraftFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "markdown" | "md" => Ok(Self::Markdown),
+            "text" | "txt" => Ok(Self::Text),
+            _ => Err(format!(
+                "unknown format `{s}` (expected one of: markdown, text)"
+            )),
+        }
+    }
+}
+
+/// Errors that can occur while gathering a [`ClaimRequest`].
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum ClaimError {
+    /// The name is not held by someone else's crate, so there is nothing to
+    /// claim.
+    NotTaken(Availability),
+    /// The name could not be checked.
+    Check(CheckError),
+}
+
+impl fmt::Display for ClaimError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotTaken(availability) => {
+                write!(f, "the name is {availability}, there is nothing to claim")
+            }
+            Self::Check(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl std::error::Error for ClaimError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::NotTaken(_) => None,
+            Self::Check(e) => Some(e),
+        }
+    }
+}
+
+impl From<CheckError> for ClaimError {
+    fn from(e: CheckError) -> Self {
+        Self::Check(e)
+    }
+}
+
+/// The facts behind a name-transfer request for one crate.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct ClaimRequest {
+    /// The crate's name, spelled as published.
+    pub name: String,
+    /// The crate's user and team owners.
+    pub owners: Vec<Owner>,
+    /// Metadata and publish history of the crate.
+    pub details: CrateDetails,
+    /// Whether the crate's repository still exists.
+    pub repository: RepositoryStatus,
+    /// Why the crate looks dormant (or not).
+    pub classification: Classification,
+}
+
+impl ClaimRequest {
+    /// Look up everything a transfer request needs to mention about a taken
+    /// name.
+    ///
+    /// Only reads from crates.io; the repository is left
+    /// [`RepositoryStatus::Unchecked`] unless you then call
+    /// [`check_repository`](Self::check_repository).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ClaimError::NotTaken`] if the name is available, reserved or
+    /// already yours, and [`ClaimError::Check`] if it is invalid or the API
+    /// cannot be queried.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use cargo_avail::check::Client;
+    /// use cargo_avail::claim::{ClaimRequest, DraftFormat};
+    /// use cargo_avail::classify::ClassifyOptions;
+    ///
+    /// let client = Client::new();
+    /// let request = ClaimRequest::gather(&client, "old-crate", &ClassifyOptions::default())
+    ///     .unwrap();
+    /// print!("{}", request.render(DraftFormat::Markdown));
+    /// ```
+    pub fn gather(
+        client: &Client,
+        name: &str,
+        options: &ClassifyOptions,
+    ) -> Result<Self, ClaimError> {
+        let report = check_name_report(client, name)?;
+        let (Availability::Taken, Some(details), Some(collision)) =
+            (&report.availability, report.details, report.collision)
+        else {
+            return Err(ClaimError::NotTaken(report.availability));
+        };
+        let owners = crate_owners(client, &collision.name)?.unwrap_or_default();
+        let repository = match details.repository.as_deref().map(str::trim) {
+            None | Some("") => RepositoryStatus::Missing,
+            Some(_) => RepositoryStatus::Unchecked,
+        };
+        let classification = classify(&details, options);
+        Ok(Self {
+            name: collision.name,
+            owners,
+            details,
+            repository,
+            classification,
+        })
+    }
+
+    /// Send a `HEAD` request to the crate's repository URL to see whether it
+    /// still exists, and record the answer in [`repository`](Self::repository).
+    ///
+    /// The repository is usually on a third-party host, so this is never done
+    /// by [`gather`](Self::gather) itself.
+    pub fn check_repository(&mut self, client: &Client) {
+        self.repository = match self.details.repository.as_deref().map(str::trim) {
+            None | Some("") => RepositoryStatus::Missing,
+            Some(url) if url.starts_with("https://") || url.starts_with("http://") => {
+                match client.url_exists(url) {
+                    Ok(true) => RepositoryStatus::Reachable,
+                    Ok(false) => RepositoryStatus::Gone,
+                    Err(_) => RepositoryStatus::Unknown,
+                }
+            }
+            Some(_) => RepositoryStatus::Unknown,
+        };
+    }
+
+    /// Render the request as a draft email to the crates.io team.
+    ///
+    /// Placeholders in `<angle brackets>` mark what only you can fill in: how
+    /// you tried to reach the owners, and what you plan to publish.
+    #[must_use]
+    pub fn render(&self, format: DraftFormat) -> String {
+        let name = code(&self.name, format);
+        let mut out = String::new();
+        let _ = writeln!(out, "To: {HELP_ADDRESS}");
+        let _ = writeln!(out, "Subject: Request to transfer the crate name {name}");
+        out.push('\n');
+        out.push_str("Hello crates.io team,\n\n");
+
+        let assessment = match self.classification.label {
+            Label::LikelyPlaceholder => "appears to be an unused placeholder",
+            Label::Dormant => "appears to be unmaintained",
+            _ => "may no longer be maintained",
+        };
+        let _ = write!(
+            out,
+            "I would like to request ownership of the crate name {name} \
+             (https://crates.io/crates/{}), which {assessment}",
+            self.name
+        );
+        if self.classification.reasons.is_empty() {
+            out.push_str(".\n\n");
+        } else {
+            let _ = writeln!(out, ": {}.\n", self.classification.reasons.join("; "));
+        }
+
+        let _ = writeln!(out, "{}\n", heading("The existing crate", format));
+        self.write_facts(&mut out);
+        if !self.details.releases.is_empty() {
+            let _ = writeln!(out, "\n{}\n", heading("Publish history", format));
+            self.write_history(&mut out);
+        }
+
+        let _ = writeln!(out, "\n{}\n", heading("Contacting the owners", format));
+        let _ = writeln!(
+            out,
+            "As the crates.io usage policy ({POLICY_URL}) asks, I tried to reach \
+             the current owners before writing to you:\n"
+        );
+        out.push_str(
+            "- <date>: <how you reached out, e.g. a link to a GitHub issue or the address you emailed>\n",
+        );
+        out.push_str("- <date>: <any follow-up, and whether anyone replied>\n");
+
+        let _ = writeln!(out, "\n{}\n", heading("Intended use", format));
+        out.push_str("<What you plan to publish under this name, with a link to the project.>\n\n");
+        out.push_str("Thank you,\n<your name> (<your crates.io login>)\n");
+        out
+    }
+
+    fn write_facts(&self, out: &mut String) {
+        let details = &self.details;
+        let owners = if self.owners.is_empty() {
+            "none listed".to_string()
+        } else {
+            self.owners
+                .iter()
+                .map(|o| format!("{} ({})", o.login, o.kind))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        let _ = writeln!(out, "- Owners: {owners}");
+        let _ = writeln!(out, "- First published: {}", date(&details.created_at));
+        let _ = writeln!(out, "- Last updated: {}", date(&details.updated_at));
+        let _ = writeln!(
+            out,
+            "- Versions: {} ({} yanked), latest {}",
+            details.versions, details.yanked_versions, details.max_version
+        );
+        let _ = match details.recent_downloads {
+            Some(recent) => writeln!(
+                out,
+                "- Downloads: {} total, {recent} in the last 90 days",
+                details.downloads
+            ),
+            None => writeln!(out, "- Downloads: {} total", details.downloads),
+        };
+        let _ = match details.repository.as_deref().map(str::trim) {
+            Some(url) if !url.is_empty() => {
+                writeln!(out, "- Repository: {url} ({})", self.repository)
+            }
+            _ => writeln!(out, "- Repository: {}", self.repository),
+        };
+        // Descriptions are free text; keep them on one line
+        let description = details
+            .description
+            .as_deref()
+            .map(|d| d.split_whitespace().collect::<Vec<_>>().join(" "))
+            .filter(|d| !d.is_empty())
+            .unwrap_or_else(|| "none".to_string());
+        let _ = writeln!(out, "- Description: {description}");
+    }
+
+    fn write_history(&self, out: &mut String) {
+        let releases = &self.details.releases;
+        for release in releases.iter().take(MAX_HISTORY) {
+            let yanked = if release.yanked { ", yanked" } else { "" };
+            let _ = writeln!(
+                out,
+                "- {} ({}{yanked})",
+                release.num,
+                date(&release.created_at)
+            );
+        }
+        if releases.len() > MAX_HISTORY {
+            let _ = writeln!(
+                out,
+                "- ... and {} earlier releases",
+                releases.len() - MAX_HISTORY
+            );
+        }
+    }
+}
+
+fn code(s: &str, format: DraftFormat) -> String {
+    match format {
+        DraftFormat::Markdown => format!("`{s}`"),
+        DraftFormat::Text => format!("\"{s}\""),
+    }
+}
+
+fn heading(title: &str, format: DraftFormat) -> String {
+    match format {
+        DraftFormat::Markdown => format!("## {title}"),
+        DraftFormat::Text => format!("{title}:"),
+    }
+}
+
+/// The date part of an RFC 3339 timestamp.
+fn date(timestamp: &str) -> &str {
+    timestamp.get(..10).unwrap_or(timestamp)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::check::Release;
+
+    fn request() -> ClaimRequest {
+        let releases = (0..12)
+            .rev()
+            .map(|minor| Release {
+                num: format!("0.{minor}.0"),
+                created_at: format!("2016-03-{:02}T00:00:00+00:00", minor + 1),
+                yanked: minor == 11,
+            })
+            .collect();
+        ClaimRequest {
+            name: "old_thing".to_string(),
+            owners: serde_json::from_str(
+                r#"[{"login":"alice","kind":"user"},{"login":"github:acme:owners","kind":"team"}]"#,
+            )
+            .unwrap(),
+            details: CrateDetails {
+                description: Some("Reserved\nfor later".to_string()),
+                created_at: "2016-03-01T00:00:00+00:00".to_string(),
+                updated_at: "2016-03-12T00:00:00+00:00".to_string(),
+                max_version: "0.10.0".to_string(),
+                downloads: 1234,
+                recent_downloads: Some(12),
+                repository: None,
+                homepage: None,
+                versions: 12,
+                yanked_versions: 1,
+                releases,
+            },
+            repository: RepositoryStatus::Missing,
+            classification: Classification {
+                label: Label::LikelyPlaceholder,
+                score: 7,
+                reasons: vec![
+                    "description mentions \"reserved\"".to_string(),
+                    "no repository".to_string(),
+                ],
+            },
+        }
+    }
+
+    #[test]
+    fn markdown_draft_lists_the_evidence() {
+        let draft = request().render(DraftFormat::Markdown);
+        assert!(draft.starts_with("To: help@crates.io\n"), "{draft}");
+        assert!(draft.contains("Subject: Request to transfer the crate name `old_thing`"));
+        assert!(draft.contains("appears to be an unused placeholder: description mentions"));
+        assert!(draft.contains("- Owners: alice (user), github:acme:owners (team)"));
+        assert!(draft.contains("- Downloads: 1234 total, 12 in the last 90 days"));
+        assert!(draft.contains("- Repository: none listed"));
+        assert!(draft.contains("- Description: Reserved for later"));
+        assert!(draft.contains("## Contacting the owners"));
+        assert!(draft.contains("<your crates.io login>"));
+    }
+
+    #[test]
+    fn publish_history_is_capped() {
+        let draft = request().render(DraftFormat::Markdown);
+        assert!(draft.contains("- 0.11.0 (2016-03-12, yanked)"), "{draft}");
+        assert!(draft.contains("- 0.2.0 (2016-03-03)"), "{draft}");
+        assert!(!draft.contains("- 0.1.0 ("), "{draft}");
+        assert!(draft.contains("- ... and 2 earlier releases"), "{draft}");
+    }
+
+    #[test]
+    fn text_draft_has_no_markdown() {
+        let draft = request().render(DraftFormat::Text);
+        assert!(!draft.contains('`'), "{draft}");
+        assert!(!draft.contains("## "), "{draft}");
+        assert!(draft.contains("Contacting the owners:\n"), "{draft}");
+        assert!(draft.contains("crate name \"old_thing\""), "{draft}");
+    }
+
+    #[test]
+    fn repository_status_is_shown_next_to_the_url() {
+        let mut request = request();
+        request.details.repository = Some("https://github.com/alice/old-thing".to_string());
+        request.repository = RepositoryStatus::Gone;
+        let draft = request.render(DraftFormat::Markdown);
+        assert!(
+            draft.contains("- Repository: https://github.com/alice/old-thing (no longer exists)"),
+            "{draft}"
+        );
+    }
+
+    #[test]
+    fn unvisited_repository_is_marked_unchecked() {
+        let mut request = request();
+        request.details.repository = Some("https://github.com/alice/old-thing".to_string());
+        request.repository = RepositoryStatus::Unchecked;
+        let draft = request.render(DraftFormat::Markdown);
+        assert!(
+            draft.contains("- Repository: https://github.com/alice/old-thing (status: unchecked)"),
+            "{draft}"
+        );
+    }
+
+    #[test]
+    fn draft_formats_parse() {
+        assert_eq!("markdown".parse(), Ok(DraftFormat::Markdown));
+        assert_eq!("text".parse(), Ok(DraftFormat::Text));
+        assert!("html".parse::<DraftFormat>().is_err());
+    }
+}
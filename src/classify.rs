@@ -0,0 +1,293 @@
+//! Heuristics for spotting abandoned or placeholder crates.
+//!
+//! crates.io lets people request the transfer of abandoned names, but finding
+//! candidates means reading each crate page by hand. [`classify`] scores a
+//! taken crate's [`CrateDetails`] against a handful of signals (a lone `0.1.0`
+//! release, no description, no repository, years without an update, negligible
+//! downloads, nothing but yanked versions) and labels it accordingly.
+
+use std::fmt;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::Serialize;
+
+use crate::check::CrateDetails;
+
+/// Versions that crates.io name reservations are typically published as.
+const PLACEHOLDER_VERSIONS: &[&str] = &["0.0.0", "0.0.1", "0.1.0"];
+
+/// Words in a description that announce a placeholder.
+const PLACEHOLDER_WORDS: &[&str] = &["placeholder", "reserved", "squat", "coming soon"];
+
+/// Descriptions shorter than this say nothing about the crate.
+const MIN_DESCRIPTION_LENGTH: usize = 10;
+
+/// Scores at or above this are labeled [`Label::LikelyPlaceholder`].
+const PLACEHOLDER_SCORE: u32 = 5;
+
+/// Scores at or above this are labeled at least [`Label::Dormant`].
+const DORMANT_SCORE: u32 = 3;
+
+/// How a taken crate was classified.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+pub enum Label {
+    /// Maintained and in use.
+    Active,
+    /// Real, but not updated in a long time.
+    Dormant,
+    /// Most likely a name reservation with no real content.
+    LikelyPlaceholder,
+}
+
+impl fmt::Display for Label {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Active => write!(f, "active"),
+            Self::Dormant => write!(f, "dormant"),
+            Self::LikelyPlaceholder => write!(f, "likely-placeholder"),
+        }
+    }
+}
+
+/// Thresholds for [`classify`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClassifyOptions {
+    /// A crate not updated in this many years counts as stale.
+    pub dormant_years: u32,
+    /// Fewer recent (90-day) downloads than this counts as negligible.
+    pub min_recent_downloads: u64,
+    /// The reference time for staleness (defaults to now).
+    pub now: SystemTime,
+}
+
+impl Default for ClassifyOptions {
+    fn default() -> Self {
+        Self {
+            dormant_years: 2,
+            min_recent_downloads: 100,
+            now: SystemTime::now(),
+        }
+    }
+}
+
+/// The verdict for a taken crate, with the signals that produced it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[non_exhaustive]
+pub struct Classification {
+    /// The overall label.
+    pub label: Label,
+    /// Sum of the weights of every signal that fired.
+    pub score: u32,
+    /// One human-readable line per signal that fired.
+    pub reasons: Vec<String>,
+}
+
+/// Classify a taken crate as active, dormant or a likely placeholder.
+///
+/// ```no_run
+/// use cargo_avail::check::{Client, check_name_report};
+/// use cargo_avail::classify::{classify, ClassifyOptions, Label};
+///
+/// let report = check_name_report(&Client::new(), "some-name").unwrap();
+/// if let Some(details) = &report.details {
+///     let verdict = classify(details, &ClassifyOptions::default());
+///     if verdict.label == Label::LikelyPlaceholder {
+///         println!("transfer candidate: {}", verdict.reasons.join(", "));
+///     }
+/// }
+/// ```
+#[must_use]
+pub fn classify(details: &CrateDetails, options: &ClassifyOptions) -> Classification {
+    let mut score = 0;
+    let mut reasons = Vec::new();
+    let mut signal = |weight: u32, reason: String| {
+        score += weight;
+        reasons.push(reason);
+    };
+
+    if details.all_yanked() {
+        signal(3, "every version is yanked".to_string());
+    } else if details.versions == 1 && PLACEHOLDER_VERSIONS.contains(&details.max_version.as_str())
+    {
+        signal(3, format!("only version is {}", details.max_version));
+    }
+
+    let description = details.description.as_deref().unwrap_or("").trim();
+    let lowered = description.to_lowercase();
+    if let Some(word) = PLACEHOLDER_WORDS.iter().find(|w| lowered.contains(*w)) {
+        signal(3, format!("description mentions \"{word}\""));
+    } else if description.len() < MIN_DESCRIPTION_LENGTH {
+        signal(1, "empty or near-empty description".to_string());
+    }
+
+    if details.repository.as_deref().is_none_or(str::is_empty) {
+        signal(1, "no repository".to_string());
+    }
+
+    let stale_years = years_since(&details.updated_at, options.now);
+    let stale = stale_years.is_some_and(|years| years >= options.dormant_years);
+    if let Some(years) = stale_years.filter(|_| stale) {
+        signal(2, format!("not updated in {years} years"));
+    }
+
+    if details
+        .recent_downloads
+        .is_some_and(|recent| recent < options.min_recent_downloads)
+    {
+        signal(1, "negligible recent downloads".to_string());
+    }
+
+    let label = if score >= PLACEHOLDER_SCORE {
+        Label::LikelyPlaceholder
+    } else if stale || score >= DORMANT_SCORE {
+        Label::Dormant
+    } else {
+        Label::Active
+    };
+    Classification {
+        label,
+        score,
+        reasons,
+    }
+}
+
+/// Whole years between an RFC 3339 timestamp and `now`, or `None` if the
+/// timestamp cannot be parsed.
+fn years_since(timestamp: &str, now: SystemTime) -> Option<u32> {
+    let then = days_from_date(timestamp.get(..10)?)?;
+    let now = i64::try_from(now.duration_since(UNIX_EPOCH).ok()?.as_secs() / 86_400).ok()?;
+    // Average Gregorian year length; off-by-a-day precision is plenty here.
+    u32::try_from((now - then) * 400 / 146_097).ok()
+}
+
+/// Days since the Unix epoch for a `YYYY-MM-DD` date (proleptic Gregorian).
+fn days_from_date(date: &str) -> Option<i64> {
+    let mut parts = date.splitn(3, '-');
+    let y: i64 = parts.next()?.parse().ok()?;
+    let m: i64 = parts.next()?.parse().ok()?;
+    let d: i64 = parts.next()?.parse().ok()?;
+    if !(1..=12).contains(&m) || !(1..=31).contains(&d) {
+        return None;
+    }
+    // Howard Hinnant's days_from_civil
+    let y = if m <= 2 { y - 1 } else { y };
+    let era = y.div_euclid(400);
+    let year_of_era = y - era * 400;
+    let day_of_year = (153 * ((m + 9) % 12) + 2) / 5 + d - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    Some(era * 146_097 + day_of_era - 719_468)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    /// 2026-01-01T00:00:00Z
+    const NOW: u64 = 1_767_225_600;
+
+    fn options() -> ClassifyOptions {
+        ClassifyOptions {
+            now: UNIX_EPOCH + Duration::from_secs(NOW),
+            ..ClassifyOptions::default()
+        }
+    }
+
+    fn active_crate() -> CrateDetails {
+        CrateDetails {
+            description: Some("A generic serialization framework".to_string()),
+            created_at: "2014-12-05T20:20:39+00:00".to_string(),
+            updated_at: "2025-11-20T10:00:00+00:00".to_string(),
+            max_version: "1.0.219".to_string(),
+            downloads: 500_000_000,
+            recent_downloads: Some(80_000_000),
+            repository: Some("https://github.com/serde-rs/serde".to_string()),
+            homepage: None,
+            versions: 300,
+            yanked_versions: 4,
+            releases: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn maintained_crate_is_active() {
+        let verdict = classify(&active_crate(), &options());
+        assert_eq!(verdict.label, Label::Active);
+        assert!(verdict.reasons.is_empty());
+    }
+
+    #[test]
+    fn old_reservation_is_likely_placeholder() {
+        let details = CrateDetails {
+            description: Some("Reserved for future use".to_string()),
+            updated_at: "2016-03-01T00:00:00+00:00".to_string(),
+            max_version: "0.1.0".to_string(),
+            recent_downloads: Some(12),
+            repository: None,
+            versions: 1,
+            yanked_versions: 0,
+            ..active_crate()
+        };
+        let verdict = classify(&details, &options());
+        assert_eq!(verdict.label, Label::LikelyPlaceholder);
+        assert!(
+            verdict
+                .reasons
+                .contains(&"only version is 0.1.0".to_string())
+        );
+        assert!(
+            verdict
+                .reasons
+                .contains(&"not updated in 9 years".to_string())
+        );
+        assert!(verdict.reasons.contains(&"no repository".to_string()));
+    }
+
+    #[test]
+    fn stale_real_crate_is_dormant() {
+        let details = CrateDetails {
+            updated_at: "2021-06-01T00:00:00+00:00".to_string(),
+            ..active_crate()
+        };
+        let verdict = classify(&details, &options());
+        assert_eq!(verdict.label, Label::Dormant);
+        assert_eq!(verdict.score, 2);
+    }
+
+    #[test]
+    fn yanked_only_is_a_strong_signal() {
+        let details = CrateDetails {
+            versions: 3,
+            yanked_versions: 3,
+            description: None,
+            ..active_crate()
+        };
+        let verdict = classify(&details, &options());
+        assert_eq!(verdict.label, Label::Dormant);
+        assert_eq!(verdict.reasons[0], "every version is yanked");
+    }
+
+    #[test]
+    fn dormant_years_is_configurable() {
+        let details = CrateDetails {
+            updated_at: "2024-06-01T00:00:00+00:00".to_string(),
+            ..active_crate()
+        };
+        assert_eq!(classify(&details, &options()).label, Label::Active);
+        let strict = ClassifyOptions {
+            dormant_years: 1,
+            ..options()
+        };
+        assert_eq!(classify(&details, &strict).label, Label::Dormant);
+    }
+
+    #[test]
+    fn days_from_date_matches_known_dates() {
+        assert_eq!(days_from_date("1970-01-01"), Some(0));
+        assert_eq!(days_from_date("2000-03-01"), Some(11_017));
+        assert_eq!(days_from_date("2026-01-01"), Some(20_454));
+        assert_eq!(days_from_date("not-a-date"), None);
+    }
+}
@@ -0,0 +1,242 @@
+//! Candidate names from the Cartesian product of wordlists.
+//!
+//! Brainstorming at scale starts from lists of adjectives, nouns and domain
+//! terms. [`Combinations`] joins one word from each list, in list order, and
+//! prunes the results offline: names that are too long, fail crates.io
+//! validation, are reserved, or duplicate an earlier name canonically never
+//! reach the API. Products are generated lazily, so even large ones can be
+//! streamed, and [`Combinations::sample`] draws a reproducible random sample
+//! from a seed.
+
+use std::collections::HashSet;
+
+use crate::check::validation::validate_crate_name;
+use crate::check::{canon_crate_name, is_reserved};
+use crate::rng::SplitMix64;
+
+/// Draws in a row that may add no name before [`Combinations::sample`] gives
+/// up, so a product that pruning has all but emptied cannot keep it drawing
+/// (and remembering positions) without bound.
+pub const MAX_FRUITLESS_DRAWS: usize = 10_000;
+
+/// Joins one word from each of several wordlists into candidate names.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Combinations {
+    lists: Vec<Vec<String>>,
+    separator: String,
+    max_len: Option<usize>,
+}
+
+impl Combinations {
+    /// Combine `lists` in order, joining the words with `separator`.
+    #[must_use]
+    pub fn new(lists: Vec<Vec<String>>, separator: &str) -> Self {
+        Self {
+            lists,
+            separator: separator.to_string(),
+            max_len: None,
+        }
+    }
+
+    /// Skip names longer than `max_len` characters.
+    #[must_use]
+    pub fn with_max_len(mut self, max_len: usize) -> Self {
+        self.max_len = Some(max_len);
+        self
+    }
+
+    /// The size of the product before pruning, saturating at `u64::MAX`.
+    /// Zero if there are no lists or any list is empty.
+    #[must_use]
+    pub fn total(&self) -> u64 {
+        if self.lists.is_empty() {
+            return 0;
+        }
+        self.lists.iter().fold(1u64, |total, list| {
+            total.saturating_mul(u64::try_from(list.len()).unwrap_or(u64::MAX))
+        })
+    }
+
+    /// Every surviving name, in product order (the last list varies fastest).
+    ///
+    /// ```
+    /// use cargo_avail::combine::Combinations;
+    ///
+    /// let lists = vec![
+    ///     vec!["fast".to_string(), "tiny".to_string()],
+    ///     vec!["json".to_string(), "std".to_string()],
+    /// ];
+    /// let names: Vec<String> = Combinations::new(lists, "-").iter().collect();
+    /// assert_eq!(names, ["fast-json", "fast-std", "tiny-json", "tiny-std"]);
+    /// ```
+    pub fn iter(&self) -> impl Iterator<Item = String> + '_ {
+        let mut seen = HashSet::new();
+        (0..self.total())
+            .map(|index| self.name_at(index))
+            .filter(move |name| self.keeps(name) && seen.insert(canon_crate_name(name)))
+    }
+
+    /// A reproducible random sample of up to `count` surviving names, in
+    /// product order.
+    ///
+    /// Positions in the product are drawn without replacement until `count`
+    /// names survive pruning, the product is exhausted, or
+    /// [`MAX_FRUITLESS_DRAWS`] draws in a row add no name. The sample can
+    /// therefore come up short when pruning leaves only a sliver of a large
+    /// product. The same lists, options and `seed` always give the same
+    /// sample.
+    ///
+    /// ```
+    /// use cargo_avail::combine::Combinations;
+    ///
+    /// let words = |w: &[&str]| w.iter().map(ToString::to_string).collect();
+    /// let combinations = Combinations::new(vec![words(&["a", "b", "c"]), words(&["x", "y"])], "-");
+    /// let sample = combinations.sample(3, 42);
+    /// assert_eq!(sample.len(), 3);
+    /// assert_eq!(sample, combinations.sample(3, 42));
+    /// ```
+    #[must_use]
+    pub fn sample(&self, count: usize, seed: u64) -> Vec<String> {
+        let total = self.total();
+        let mut rng = SplitMix64(seed);
+        let mut drawn = HashSet::new();
+        let mut canonical = HashSet::new();
+        let mut picked = Vec::new();
+        let mut fruitless = 0;
+        while picked.len() < count
+            && (drawn.len() as u64) < total
+            && fruitless < MAX_FRUITLESS_DRAWS
+        {
+            fruitless += 1;
+            let index = rng.below(total);
+            if !drawn.insert(index) {
+                continue;
+            }
+            let name = self.name_at(index);
+            if self.keeps(&name) && canonical.insert(canon_crate_name(&name)) {
+                picked.push((index, name));
+                fruitless = 0;
+            }
+        }
+        picked.sort_unstable_by_key(|&(index, _)| index);
+        picked.into_iter().map(|(_, name)| name).collect()
+    }
+
+    /// The name at `index` in product order.
+    fn name_at(&self, mut index: u64) -> String {
+        let mut words = Vec::with_capacity(self.lists.len());
+        for list in self.lists.iter().rev() {
+            let len = list.len() as u64;
+            // `index < total`, so the remainder always fits in usize
+            words.push(list[usize::try_from(index % len).unwrap_or_default()].as_str());
+            index /= len;
+        }
+        words.reverse();
+        words.join(&self.separator)
+    }
+
+    /// Whether `name` survives offline pruning.
+    fn keeps(&self, name: &str) -> bool {
+        self.max_len.is_none_or(|max| name.chars().count() <= max)
+            && validate_crate_name(name).is_ok()
+            && !is_reserved(name)
+    }
+}
+
+/// Parse a wordlist: one word per line, ignoring blank lines and `#` comments.
+///
+/// ```
+/// use cargo_avail::combine::parse_wordlist;
+///
+/// assert_eq!(parse_wordlist("# nouns\nparser\n\n  lexer \n"), ["parser", "lexer"]);
+/// ```
+#[must_use]
+pub fn parse_wordlist(text: &str) -> Vec<String> {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(ToString::to_string)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn words(list: &[&str]) -> Vec<String> {
+        list.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn product_is_in_order_with_last_list_fastest() {
+        let combinations = Combinations::new(
+            vec![words(&["a", "b"]), words(&["x"]), words(&["1", "2"])],
+            "_",
+        );
+        assert_eq!(combinations.total(), 4);
+        assert_eq!(
+            combinations.iter().collect::<Vec<_>>(),
+            ["a_x_1", "a_x_2", "b_x_1", "b_x_2"]
+        );
+    }
+
+    #[test]
+    fn empty_lists_give_nothing() {
+        assert_eq!(Combinations::new(Vec::new(), "-").total(), 0);
+        let combinations = Combinations::new(vec![words(&["a"]), Vec::new()], "-");
+        assert_eq!(combinations.total(), 0);
+        assert_eq!(combinations.iter().count(), 0);
+        assert!(combinations.sample(5, 1).is_empty());
+    }
+
+    #[test]
+    fn pruning_drops_long_invalid_reserved_and_duplicate_names() {
+        let combinations = Combinations::new(
+            vec![
+                words(&["std", "9", "ok", "verylongword", "compiler"]),
+                words(&["", "x"]),
+            ],
+            "",
+        )
+        .with_max_len(8);
+        // `std` is reserved, `9` starts with a digit, and `verylongword` and
+        // `compilerx` are too long
+        assert_eq!(
+            combinations.iter().collect::<Vec<_>>(),
+            ["stdx", "ok", "okx", "compiler"]
+        );
+        let combinations = Combinations::new(vec![words(&["a-b", "a_b"]), words(&["c"])], "-");
+        assert_eq!(combinations.iter().collect::<Vec<_>>(), ["a-b-c"]);
+    }
+
+    #[test]
+    fn samples_are_reproducible_and_distinct() {
+        let nouns: Vec<String> = (0..50).map(|i| format!("n{i}")).collect();
+        let combinations = Combinations::new(vec![words(&["a", "b", "c", "d"]), nouns], "-");
+        let sample = combinations.sample(20, 42);
+        assert_eq!(sample.len(), 20);
+        assert_eq!(sample, combinations.sample(20, 42));
+        assert_ne!(sample, combinations.sample(20, 43));
+        assert_eq!(sample.iter().collect::<HashSet<_>>().len(), 20);
+        // In product order
+        let all: Vec<String> = combinations.iter().collect();
+        let positions: Vec<usize> = sample
+            .iter()
+            .map(|name| all.iter().position(|n| n == name).unwrap())
+            .collect();
+        assert!(positions.windows(2).all(|w| w[0] < w[1]));
+    }
+
+    #[test]
+    fn oversized_samples_take_every_survivor() {
+        let combinations = Combinations::new(vec![words(&["std", "a", "b"]), words(&[""])], "");
+        assert_eq!(combinations.sample(10, 7), ["a", "b"]);
+    }
+
+    #[test]
+    fn samples_stop_when_pruning_leaves_nothing() {
+        let long: Vec<String> = (0..10_000).map(|i| format!("word{i}")).collect();
+        let combinations = Combinations::new(vec![long.clone(), long], "-").with_max_len(4);
+        assert!(combinations.sample(5, 1).is_empty());
+    }
+}
@@ -0,0 +1,59 @@
+//! Read cargo configuration files the way cargo finds them.
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// Errors that can occur while reading cargo configuration.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum ConfigError {
+    /// A cargo configuration file could not be read.
+    Io(PathBuf, std::io::Error),
+    /// A cargo configuration file is not valid TOML.
+    Parse(PathBuf, toml::de::Error),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(path, e) => write!(f, "reading {}: {e}", path.display()),
+            Self::Parse(path, e) => write!(f, "parsing {}: {e}", path.display()),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(_, e) => Some(e),
+            Self::Parse(_, e) => Some(e),
+        }
+    }
+}
+
+/// `$CARGO_HOME`, defaulting to `~/.cargo`.
+pub(crate) fn cargo_home() -> Option<PathBuf> {
+    if let Some(home) = std::env::var_os("CARGO_HOME") {
+        return Some(PathBuf::from(home));
+    }
+    let home = std::env::var_os("HOME").or_else(|| std::env::var_os("USERPROFILE"))?;
+    Some(Path::new(&home).join(".cargo"))
+}
+
+/// Parse the first of `names` that exists in `dir`.
+pub(crate) fn read_toml(dir: &Path, names: &[&str]) -> Result<Option<toml::Table>, ConfigError> {
+    for name in names {
+        let path = dir.join(name);
+        match std::fs::read_to_string(&path) {
+            Ok(contents) => {
+                return contents
+                    .parse::<toml::Table>()
+                    .map(Some)
+                    .map_err(|e| ConfigError::Parse(path, e));
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => return Err(ConfigError::Io(path, e)),
+        }
+    }
+    Ok(None)
+}
@@ -0,0 +1,219 @@
+//! Locate the crates.io API token the way cargo does.
+//!
+//! Sources are tried in order:
+//! 1. The `CARGO_REGISTRY_TOKEN` environment variable.
+//! 2. The `[registry] token` in `$CARGO_HOME/credentials.toml` (or the legacy
+//!    `credentials` file), as written by `cargo login`.
+//! 3. A `cargo:token-from-stdout` credential provider configured in
+//!    `$CARGO_HOME/config.toml` (`[registries.crates-io] credential-provider`
+//!    or `[registry] global-credential-providers`).
+//!
+//! Other credential providers (`cargo:libsecret`, `cargo:macos-keychain`,
+//! `cargo:wincred`, and external provider binaries) are skipped; set
+//! `CARGO_REGISTRY_TOKEN` when using them.
+
+use std::fmt;
+use std::process::Command;
+
+use crate::config::{ConfigError, cargo_home, read_toml};
+
+const TOKEN_ENV: &str = "CARGO_REGISTRY_TOKEN";
+const TOKEN_FROM_STDOUT: &str = "cargo:token-from-stdout";
+
+/// Errors that can occur while loading a crates.io token.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum CredentialsError {
+    /// A cargo configuration or credentials file could not be read or parsed.
+    Config(ConfigError),
+    /// A `cargo:token-from-stdout` provider failed or printed nothing.
+    Provider(String),
+}
+
+impl fmt::Display for CredentialsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Config(e) => write!(f, "{e}"),
+            Self::Provider(msg) => write!(f, "credential provider: {msg}"),
+        }
+    }
+}
+
+impl std::error::Error for CredentialsError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Config(e) => Some(e),
+            Self::Provider(_) => None,
+        }
+    }
+}
+
+impl From<ConfigError> for CredentialsError {
+    fn from(e: ConfigError) -> Self {
+        Self::Config(e)
+    }
+}
+
+/// Find the crates.io token cargo would use for `cargo publish`.
+///
+/// Returns `Ok(None)` if no token is configured.
+///
+/// # Errors
+///
+/// Returns a [`CredentialsError`] if a configuration file exists but cannot be
+/// read or parsed, or if a configured `cargo:token-from-stdout` provider fails.
+pub fn cargo_token() -> Result<Option<String>, CredentialsError> {
+    if let Some(token) = std::env::var(TOKEN_ENV)
+        .ok()
+        .filter(|t| !t.trim().is_empty())
+    {
+        return Ok(Some(token.trim().to_string()));
+    }
+    let Some(cargo_home) = cargo_home() else {
+        return Ok(None);
+    };
+    if let Some(token) = read_toml(&cargo_home, &["credentials.toml", "credentials"])?
+        .as_ref()
+        .and_then(token_from_credentials)
+    {
+        return Ok(Some(token));
+    }
+    match read_toml(&cargo_home, &["config.toml", "config"])?
+        .as_ref()
+        .and_then(token_from_stdout_provider)
+    {
+        Some(command) => run_provider(&command).map(Some),
+        None => Ok(None),
+    }
+}
+
+fn token_from_credentials(credentials: &toml::Table) -> Option<String> {
+    credentials
+        .get("registry")?
+        .get("token")?
+        .as_str()
+        .map(str::trim)
+        .filter(|t| !t.is_empty())
+        .map(str::to_string)
+}
+
+/// The command of the highest-precedence `cargo:token-from-stdout` provider
+/// configured for crates.io, as program and arguments.
+fn token_from_stdout_provider(config: &toml::Table) -> Option<Vec<String>> {
+    let crates_io = config
+        .get("registries")
+        .and_then(|r| r.get("crates-io"))
+        .and_then(|r| r.get("credential-provider"))
+        .into_iter();
+    let global = config
+        .get("registry")
+        .and_then(|r| r.get("global-credential-providers"))
+        .and_then(toml::Value::as_array)
+        .into_iter()
+        // Later global providers take precedence, as in cargo
+        .flat_map(|providers| providers.iter().rev());
+    crates_io
+        .chain(global)
+        .filter_map(provider_words)
+        .find(|words| words.first().map(String::as_str) == Some(TOKEN_FROM_STDOUT))
+        .map(|words| words[1..].to_vec())
+        .filter(|command| !command.is_empty())
+}
+
+/// A provider is either a space-separated string or an array of words.
+fn provider_words(value: &toml::Value) -> Option<Vec<String>> {
+    match value {
+        toml::Value::String(s) => Some(s.split_whitespace().map(str::to_string).collect()),
+        toml::Value::Array(words) => words
+            .iter()
+            .map(|w| w.as_str().map(str::to_string))
+            .collect(),
+        _ => None,
+    }
+}
+
+fn run_provider(command: &[String]) -> Result<String, CredentialsError> {
+    let output = Command::new(&command[0])
+        .args(&command[1..])
+        .output()
+        .map_err(|e| CredentialsError::Provider(format!("running `{}`: {e}", command[0])))?;
+    if !output.status.success() {
+        return Err(CredentialsError::Provider(format!(
+            "`{}` exited with {}",
+            command[0], output.status
+        )));
+    }
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .next()
+        .map(str::trim)
+        .filter(|t| !t.is_empty())
+        .map(str::to_string)
+        .ok_or_else(|| CredentialsError::Provider(format!("`{}` printed no token", command[0])))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn token_read_from_credentials_table() {
+        let credentials: toml::Table = "[registry]\ntoken = \"cio_abc\"\n".parse().unwrap();
+        assert_eq!(
+            token_from_credentials(&credentials).as_deref(),
+            Some("cio_abc")
+        );
+        let empty: toml::Table = "".parse().unwrap();
+        assert_eq!(token_from_credentials(&empty), None);
+    }
+
+    #[test]
+    fn crates_io_provider_takes_precedence() {
+        let config: toml::Table = r#"
+            [registry]
+            global-credential-providers = ["cargo:token", "cargo:token-from-stdout pass show global"]
+
+            [registries.crates-io]
+            credential-provider = ["cargo:token-from-stdout", "pass", "show", "crates-io"]
+        "#
+        .parse()
+        .unwrap();
+        assert_eq!(
+            token_from_stdout_provider(&config),
+            Some(vec!["pass".into(), "show".into(), "crates-io".into()])
+        );
+    }
+
+    #[test]
+    fn global_provider_used_when_no_crates_io_provider() {
+        let config: toml::Table = r#"
+            [registry]
+            global-credential-providers = ["cargo:libsecret", "cargo:token-from-stdout op read x"]
+        "#
+        .parse()
+        .unwrap();
+        assert_eq!(
+            token_from_stdout_provider(&config),
+            Some(vec!["op".into(), "read".into(), "x".into()])
+        );
+    }
+
+    #[test]
+    fn other_providers_are_ignored() {
+        let config: toml::Table = r#"
+            [registry]
+            global-credential-providers = ["cargo:token", "cargo:macos-keychain"]
+        "#
+        .parse()
+        .unwrap();
+        assert_eq!(token_from_stdout_provider(&config), None);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn provider_stdout_is_trimmed() {
+        let token = run_provider(&["echo".into(), " cio_xyz ".into()]).unwrap();
+        assert_eq!(token, "cio_xyz");
+        assert!(run_provider(&["false".into()]).is_err());
+    }
+}
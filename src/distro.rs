@@ -0,0 +1,400 @@
+//! Packaging names in Linux distributions.
+//!
+//! Debian and Fedora package crates as `rust-<name>`. Debian lowercases the
+//! name and maps `_` to `-`; Fedora keeps the crate's own spelling
+//! (`rust-serde_json`), but as crates.io does not allow both spellings, names
+//! are compared in the Debian one everywhere, so `foo_bar` and `Foo-Bar`
+//! collide exactly as they do under
+//! [`canon_crate_name`](crate::check::canon_crate_name). A name that
+//! is free on crates.io can still be a poor fit for packaging: Debian reserves
+//! `rust-<name>-<major>` for older versions of `<name>`, and the package may
+//! already exist for an unrelated project. [`distro_issues`] reports these,
+//! checking existing packages against a [`PackageList`] the user provides
+//! (from `apt-cache pkgnames`, `dnf repoquery`, or a mirror's index).
+
+use std::collections::HashSet;
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+
+/// The prefix both distributions give crate packages.
+const PACKAGE_PREFIX: &str = "rust-";
+
+/// A Linux distribution that packages crates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Distro {
+    /// Debian and its derivatives, packaged with debcargo.
+    Debian,
+    /// Fedora, packaged with rust2rpm.
+    Fedora,
+}
+
+impl Distro {
+    /// Every supported distribution.
+    pub const ALL: [Self; 2] = [Self::Debian, Self::Fedora];
+
+    /// The distribution's lowercase name, as accepted on the command line.
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Debian => "debian",
+            Self::Fedora => "fedora",
+        }
+    }
+
+    /// The source package a crate is packaged as: `rust-<name>`, lowercased
+    /// and with `_` mapped to `-`.
+    ///
+    /// ```
+    /// use cargo_avail::distro::Distro;
+    ///
+    /// assert_eq!(Distro::Debian.source_package("Serde_JSON"), "rust-serde-json");
+    /// ```
+    #[must_use]
+    pub fn source_package(self, crate_name: &str) -> String {
+        format!("{PACKAGE_PREFIX}{}", packaging_name(crate_name))
+    }
+
+    /// The binary package holding the crate's source for other packages to
+    /// build against: `librust-<name>-dev` on Debian, `rust-<name>-devel` on
+    /// Fedora.
+    #[must_use]
+    pub fn binary_package(self, crate_name: &str) -> String {
+        let name = packaging_name(crate_name);
+        match self {
+            Self::Debian => format!("librust-{name}-dev"),
+            Self::Fedora => format!("{PACKAGE_PREFIX}{name}-devel"),
+        }
+    }
+}
+
+/// Whether `c` may appear in a packaging name. Both distributions allow these
+/// in package names; the uppercase letters and `_` Fedora also allows never
+/// survive [`packaging_name`].
+fn allowed_in_package(c: char) -> bool {
+    matches!(c, 'a'..='z' | '0'..='9' | '+' | '-' | '.')
+}
+
+impl fmt::Display for Distro {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Distro {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|distro| distro.name() == s)
+            .ok_or_else(|| {
+                let known: Vec<&str> = Self::ALL.iter().map(|d| d.name()).collect();
+                format!(
+                    "unknown distribution `{s}` (expected one of: {})",
+                    known.join(", ")
+                )
+            })
+    }
+}
+
+/// Package names known to exist in a distribution.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PackageList {
+    packages: HashSet<String>,
+}
+
+impl PackageList {
+    /// Read a package list file. See [`PackageList::parse`] for the format.
+    ///
+    /// # Errors
+    ///
+    /// Returns the I/O error if the file cannot be read.
+    pub fn read(path: &Path) -> std::io::Result<Self> {
+        Ok(Self::parse(&std::fs::read_to_string(path)?))
+    }
+
+    /// Parse a package list: one package per line, as printed by
+    /// `apt-cache pkgnames` or `dnf repoquery --qf '%{name}'`. Only the
+    /// first word of each line is used, up to any `/` or `:` (as in
+    /// `apt list` output), so lists with versions or architectures work too.
+    /// Blank lines and lines starting with `#` are ignored. Names are kept
+    /// as [`packaging_name`] spells them, so Fedora's `rust-serde_json`
+    /// matches `rust-serde-json`.
+    ///
+    /// ```
+    /// use cargo_avail::distro::PackageList;
+    ///
+    /// let list = PackageList::parse("rust-serde_json\nlibrust-tokio-dev/stable 1.38.0 amd64\n");
+    /// assert!(list.contains("librust-tokio-dev"));
+    /// assert!(list.contains("rust-serde-json"));
+    /// assert!(!list.contains("rust-tokio"));
+    /// ```
+    #[must_use]
+    pub fn parse(text: &str) -> Self {
+        let packages = text
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .filter_map(|line| line.split_whitespace().next())
+            .filter_map(|word| word.split(['/', ':']).next())
+            .map(packaging_name)
+            .collect();
+        Self { packages }
+    }
+
+    /// Extend the list with the packages of another.
+    pub fn extend(&mut self, other: Self) {
+        self.packages.extend(other.packages);
+    }
+
+    /// Whether the list has a package of this name, ignoring case and
+    /// treating `_` and `-` as the same.
+    #[must_use]
+    pub fn contains(&self, package: &str) -> bool {
+        self.packages.contains(&packaging_name(package))
+    }
+
+    /// How many packages are listed.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.packages.len()
+    }
+
+    /// Whether no packages are listed.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.packages.is_empty()
+    }
+}
+
+/// What is wrong with a crate's packaging name.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum IssueKind {
+    /// The name has characters the distribution does not allow in package
+    /// names.
+    InvalidCharacters,
+    /// The name ends in `-<major>`, which debcargo uses for older major
+    /// versions of another crate.
+    VersionSuffix {
+        /// The package the name reads as a version of.
+        of: String,
+    },
+    /// A package of this name already exists.
+    Packaged,
+}
+
+impl IssueKind {
+    /// A short label for the kind of issue: `invalid`, `versioned` or
+    /// `packaged`.
+    #[must_use]
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::InvalidCharacters => "invalid",
+            Self::VersionSuffix { .. } => "versioned",
+            Self::Packaged => "packaged",
+        }
+    }
+}
+
+/// A problem with packaging a crate for a distribution.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct DistroIssue {
+    /// The distribution.
+    pub distro: Distro,
+    /// The package name concerned.
+    pub package: String,
+    /// What is wrong with it.
+    pub kind: IssueKind,
+}
+
+impl fmt::Display for DistroIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (distro, package) = (self.distro, &self.package);
+        match &self.kind {
+            IssueKind::InvalidCharacters => {
+                write!(f, "{distro}: `{package}` is not a valid package name")
+            }
+            IssueKind::VersionSuffix { of } => {
+                write!(f, "{distro}: `{package}` reads as a version of `{of}`")
+            }
+            IssueKind::Packaged => write!(f, "{distro}: `{package}` is already packaged"),
+        }
+    }
+}
+
+/// A crate name as distributions spell it: lowercased, with `_` mapped to
+/// `-`.
+///
+/// ```
+/// use cargo_avail::distro::packaging_name;
+///
+/// assert_eq!(packaging_name("Foo_Bar"), "foo-bar");
+/// ```
+#[must_use]
+pub fn packaging_name(crate_name: &str) -> String {
+    crate_name.to_lowercase().replace('_', "-")
+}
+
+/// The problems with packaging `name` for `distro`: characters its package
+/// names cannot hold, a Debian version suffix, and, given a list of existing
+/// packages, source or binary packages that already exist.
+///
+/// ```
+/// use cargo_avail::distro::{Distro, IssueKind, PackageList, distro_issues};
+///
+/// let issues = distro_issues("nom-7", Distro::Debian, None);
+/// assert_eq!(issues[0].kind, IssueKind::VersionSuffix { of: "rust-nom".to_string() });
+///
+/// let packages = PackageList::parse("rust-tokenblend\n");
+/// let issues = distro_issues("Token_Blend", Distro::Fedora, Some(&packages));
+/// assert!(issues.is_empty());
+/// let issues = distro_issues("tokenblend", Distro::Fedora, Some(&packages));
+/// assert_eq!(issues[0].to_string(), "fedora: `rust-tokenblend` is already packaged");
+/// ```
+#[must_use]
+pub fn distro_issues(
+    name: &str,
+    distro: Distro,
+    packages: Option<&PackageList>,
+) -> Vec<DistroIssue> {
+    let source = distro.source_package(name);
+    let issue = |package: &str, kind| DistroIssue {
+        distro,
+        package: package.to_string(),
+        kind,
+    };
+    let mut issues = Vec::new();
+    if !source.chars().all(allowed_in_package) {
+        issues.push(issue(&source, IssueKind::InvalidCharacters));
+    }
+    if distro == Distro::Debian {
+        if let Some(of) = version_base(&source) {
+            issues.push(issue(&source, IssueKind::VersionSuffix { of }));
+        }
+    }
+    if let Some(packages) = packages {
+        for package in [source.clone(), distro.binary_package(name)] {
+            if packages.contains(&package) {
+                issues.push(issue(&package, IssueKind::Packaged));
+            }
+        }
+    }
+    issues
+}
+
+/// The package `package` would be an older major version of under
+/// debcargo's naming, `rust-<name>-<major>`: `rust-nom` for `rust-nom-7`.
+/// Versions before 1.0 are suffixed `-0.<minor>`, which crate names cannot
+/// spell.
+fn version_base(package: &str) -> Option<String> {
+    let (base, major) = package.rsplit_once('-')?;
+    let is_major =
+        !major.is_empty() && major.bytes().all(|b| b.is_ascii_digit()) && !major.starts_with('0');
+    (is_major && base.len() > PACKAGE_PREFIX.len()).then(|| base.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds(name: &str, distro: Distro, packages: Option<&PackageList>) -> Vec<&'static str> {
+        distro_issues(name, distro, packages)
+            .iter()
+            .map(|issue| issue.kind.label())
+            .collect()
+    }
+
+    #[test]
+    fn packaging_names_collide_like_canonical_names() {
+        for distro in Distro::ALL {
+            assert_eq!(
+                distro.source_package("foo_bar"),
+                distro.source_package("Foo-Bar")
+            );
+        }
+        assert_eq!(
+            Distro::Debian.binary_package("foo_bar"),
+            "librust-foo-bar-dev"
+        );
+        assert_eq!(
+            Distro::Fedora.binary_package("foo_bar"),
+            "rust-foo-bar-devel"
+        );
+    }
+
+    #[test]
+    fn distros_parse_by_name() {
+        assert_eq!("fedora".parse(), Ok(Distro::Fedora));
+        assert!(
+            "arch"
+                .parse::<Distro>()
+                .unwrap_err()
+                .contains("debian, fedora")
+        );
+    }
+
+    #[test]
+    fn characters_outside_package_names_are_invalid() {
+        assert_eq!(kinds("foo bar", Distro::Debian, None), ["invalid"]);
+        assert_eq!(kinds("café", Distro::Fedora, None), ["invalid"]);
+        assert!(kinds("foo.bar", Distro::Debian, None).is_empty());
+    }
+
+    #[test]
+    fn only_debian_has_version_suffixes() {
+        assert_eq!(kinds("clap_2", Distro::Debian, None), ["versioned"]);
+        assert!(kinds("clap_2", Distro::Fedora, None).is_empty());
+        // No crate to be a version of, or not a major version
+        assert!(version_base("rust-2").is_none());
+        assert!(version_base("rust-foo-0").is_none());
+        assert!(version_base("rust-foo-v2").is_none());
+        assert!(version_base("rust-sha2").is_none());
+    }
+
+    #[test]
+    fn source_and_binary_packages_are_looked_up() {
+        let packages = PackageList::parse(
+            "# apt list output\n\
+             librust-acme-dev/stable,now 1.0.0-1 amd64 [installed]\n\
+             rust-acme-devel\n\
+             \n\
+             RUST-OTHER\n",
+        );
+        assert_eq!(packages.len(), 3);
+        assert_eq!(
+            distro_issues("acme", Distro::Debian, Some(&packages)),
+            [DistroIssue {
+                distro: Distro::Debian,
+                package: "librust-acme-dev".to_string(),
+                kind: IssueKind::Packaged,
+            }]
+        );
+        assert_eq!(kinds("acme", Distro::Fedora, Some(&packages)), ["packaged"]);
+        assert_eq!(
+            kinds("other", Distro::Debian, Some(&packages)),
+            ["packaged"]
+        );
+    }
+
+    #[test]
+    fn fedora_lists_keep_the_crate_spelling() {
+        // As printed by `dnf repoquery`: underscores kept
+        let packages = PackageList::parse("rust-foo_bar\nrust-foo_bar-devel\n");
+        assert_eq!(
+            distro_issues("foo_bar", Distro::Fedora, Some(&packages))
+                .iter()
+                .map(|issue| issue.package.as_str())
+                .collect::<Vec<_>>(),
+            ["rust-foo-bar", "rust-foo-bar-devel"]
+        );
+        assert_eq!(
+            kinds("Foo-Bar", Distro::Fedora, Some(&packages)),
+            ["packaged", "packaged"]
+        );
+        assert!(packages.contains("rust-foo_bar-devel"));
+    }
+}
@@ -0,0 +1,328 @@
+//! Brace and range expansion of name patterns.
+//!
+//! `acme-{http,grpc}-{client,server}` expands to the four names
+//! `acme-http-client`, `acme-http-server`, `acme-grpc-client` and
+//! `acme-grpc-server`, and `foo{1..3}` to `foo1`, `foo2` and `foo3`, the same
+//! way a shell would but without depending on one: patterns read from stdin
+//! or quoted on the command line expand too. Braces never appear in valid
+//! crate names, so a pattern cannot be mistaken for a name.
+
+use std::fmt;
+
+/// The most names a single pattern may expand to by default.
+pub const MAX_EXPANSION: usize = 1000;
+
+/// Why a pattern could not be expanded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ExpandError {
+    /// A `{` without a matching `}`, or the reverse.
+    Unbalanced {
+        /// The pattern.
+        pattern: String,
+    },
+    /// A brace group holding neither a `,` list nor a `..` range.
+    BadGroup {
+        /// The pattern.
+        pattern: String,
+        /// The group, braces included.
+        group: String,
+    },
+    /// The pattern expands to more names than allowed.
+    TooLarge {
+        /// The pattern.
+        pattern: String,
+        /// The most names allowed.
+        limit: usize,
+    },
+}
+
+impl fmt::Display for ExpandError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unbalanced { pattern } => write!(f, "unbalanced braces in `{pattern}`"),
+            Self::BadGroup { pattern, group } => write!(
+                f,
+                "`{group}` in `{pattern}` is neither a list like {{a,b}} nor a range like {{1..3}}"
+            ),
+            Self::TooLarge { pattern, limit } => {
+                write!(f, "`{pattern}` expands to more than {limit} names")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ExpandError {}
+
+/// Expand the brace lists and ranges in `pattern`, left to right.
+///
+/// Lists (`{a,b}`) may be nested and may hold empty alternatives
+/// (`foo{,-rs}`). Ranges count up or down between integers (`{1..3}`,
+/// `{10..8}`), zero-padded if either end is (`{01..10}`), or between single
+/// ASCII letters (`{a..c}`). A pattern without braces expands to itself.
+///
+/// # Errors
+///
+/// Returns [`ExpandError`] if the braces are unbalanced, a group is neither a
+/// list nor a range, or the pattern would expand to more than `limit` names.
+///
+/// ```
+/// use cargo_avail::expand::{MAX_EXPANSION, expand};
+///
+/// assert_eq!(
+///     expand("acme-{http,ws}-{client,server}", MAX_EXPANSION).unwrap(),
+///     ["acme-http-client", "acme-http-server", "acme-ws-client", "acme-ws-server"]
+/// );
+/// assert_eq!(expand("foo{1..3}", MAX_EXPANSION).unwrap(), ["foo1", "foo2", "foo3"]);
+/// assert!(expand("foo{1..3}", 2).is_err());
+/// ```
+pub fn expand(pattern: &str, limit: usize) -> Result<Vec<String>, ExpandError> {
+    let mut out = Vec::new();
+    expand_into(pattern, pattern, limit, &mut out)?;
+    Ok(out)
+}
+
+fn expand_into(
+    pattern: &str,
+    s: &str,
+    limit: usize,
+    out: &mut Vec<String>,
+) -> Result<(), ExpandError> {
+    let unbalanced = || ExpandError::Unbalanced {
+        pattern: pattern.to_string(),
+    };
+    let Some(open) = s.find('{') else {
+        if s.contains('}') {
+            return Err(unbalanced());
+        }
+        if out.len() == limit {
+            return Err(ExpandError::TooLarge {
+                pattern: pattern.to_string(),
+                limit,
+            });
+        }
+        out.push(s.to_string());
+        return Ok(());
+    };
+    if s[..open].contains('}') {
+        return Err(unbalanced());
+    }
+    let close = matching_brace(s, open).ok_or_else(unbalanced)?;
+    let body = &s[open + 1..close];
+    let too_large = || ExpandError::TooLarge {
+        pattern: pattern.to_string(),
+        limit,
+    };
+    let alternatives: Vec<String> = match split_top_level(body) {
+        list if list.len() > 1 => list.into_iter().map(str::to_string).collect(),
+        _ => {
+            let range = Range::parse(body).ok_or_else(|| ExpandError::BadGroup {
+                pattern: pattern.to_string(),
+                group: s[open..=close].to_string(),
+            })?;
+            // Checked before the items are built, so `{1..9999999999}` fails fast
+            if usize::try_from(range.len()).map_or(true, |len| len > limit) {
+                return Err(too_large());
+            }
+            range.items()
+        }
+    };
+    let (prefix, suffix) = (&s[..open], &s[close + 1..]);
+    for alternative in alternatives {
+        expand_into(
+            pattern,
+            &format!("{prefix}{alternative}{suffix}"),
+            limit,
+            out,
+        )?;
+    }
+    Ok(())
+}
+
+/// The index of the `}` closing the `{` at `open`.
+fn matching_brace(s: &str, open: usize) -> Option<usize> {
+    let mut depth = 0usize;
+    for (i, c) in s.char_indices().skip_while(|&(i, _)| i < open) {
+        match c {
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(i);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Split a list body at the commas outside nested braces.
+fn split_top_level(body: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let (mut depth, mut start) = (0usize, 0);
+    for (i, c) in body.char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                parts.push(&body[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&body[start..]);
+    parts
+}
+
+/// A `start..end` range inside braces.
+enum Range {
+    /// Integers, zero-padded to `width` digits.
+    Numbers { start: i64, end: i64, width: usize },
+    /// Single ASCII letters.
+    Letters { start: u8, end: u8 },
+}
+
+impl Range {
+    fn parse(body: &str) -> Option<Self> {
+        let (start, end) = body.split_once("..")?;
+        if let (Ok(a), Ok(b)) = (start.parse(), end.parse()) {
+            let padded = |s: &str| {
+                let digits = s.trim_start_matches('-');
+                digits.len() > 1 && digits.starts_with('0')
+            };
+            let width = if padded(start) || padded(end) {
+                start.len().max(end.len())
+            } else {
+                0
+            };
+            return Some(Self::Numbers {
+                start: a,
+                end: b,
+                width,
+            });
+        }
+        match (start.as_bytes(), end.as_bytes()) {
+            (&[a], &[b]) if a.is_ascii_alphabetic() && b.is_ascii_alphabetic() => {
+                Some(Self::Letters { start: a, end: b })
+            }
+            _ => None,
+        }
+    }
+
+    /// How many items the range holds.
+    fn len(&self) -> u64 {
+        match *self {
+            Self::Numbers { start, end, .. } => start.abs_diff(end).saturating_add(1),
+            Self::Letters { start, end } => u64::from(start.abs_diff(end)) + 1,
+        }
+    }
+
+    /// The items in order, counting down if the range does.
+    fn items(&self) -> Vec<String> {
+        let (mut items, descending): (Vec<String>, bool) = match *self {
+            Self::Numbers { start, end, width } => (
+                (start.min(end)..=start.max(end))
+                    .map(|n| format!("{n:0width$}"))
+                    .collect(),
+                start > end,
+            ),
+            Self::Letters { start, end } => (
+                (start.min(end)..=start.max(end))
+                    .map(|c| char::from(c).to_string())
+                    .collect(),
+                start > end,
+            ),
+        };
+        if descending {
+            items.reverse();
+        }
+        items
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn expanded(pattern: &str) -> Vec<String> {
+        expand(pattern, MAX_EXPANSION).unwrap()
+    }
+
+    #[test]
+    fn plain_names_expand_to_themselves() {
+        assert_eq!(expanded("serde"), ["serde"]);
+        assert_eq!(expanded("a..b"), ["a..b"]);
+    }
+
+    #[test]
+    fn lists_expand_left_to_right() {
+        assert_eq!(
+            expanded("acme-{http,grpc,ws}-{client,server}"),
+            [
+                "acme-http-client",
+                "acme-http-server",
+                "acme-grpc-client",
+                "acme-grpc-server",
+                "acme-ws-client",
+                "acme-ws-server",
+            ]
+        );
+    }
+
+    #[test]
+    fn lists_nest_and_allow_empty_alternatives() {
+        assert_eq!(expanded("foo{,-rs}"), ["foo", "foo-rs"]);
+        assert_eq!(expanded("x-{a,b{1,2}}"), ["x-a", "x-b1", "x-b2"]);
+    }
+
+    #[test]
+    fn numeric_ranges_count_both_ways_and_pad() {
+        assert_eq!(expanded("foo{1..3}"), ["foo1", "foo2", "foo3"]);
+        assert_eq!(expanded("v{3..1}"), ["v3", "v2", "v1"]);
+        assert_eq!(expanded("n{08..10}"), ["n08", "n09", "n10"]);
+        assert_eq!(expanded("n{-1..1}"), ["n-1", "n0", "n1"]);
+    }
+
+    #[test]
+    fn letter_ranges() {
+        assert_eq!(expanded("x{a..c}"), ["xa", "xb", "xc"]);
+        assert_eq!(expanded("x{C..A}"), ["xC", "xB", "xA"]);
+    }
+
+    #[test]
+    fn malformed_patterns_are_rejected() {
+        assert!(matches!(
+            expand("foo{a,b", MAX_EXPANSION),
+            Err(ExpandError::Unbalanced { .. })
+        ));
+        assert!(matches!(
+            expand("foo}a{", MAX_EXPANSION),
+            Err(ExpandError::Unbalanced { .. })
+        ));
+        let err = expand("foo{bar}", MAX_EXPANSION).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "`{bar}` in `foo{bar}` is neither a list like {a,b} nor a range like {1..3}"
+        );
+        assert!(expand("foo{a..zz}", MAX_EXPANSION).is_err());
+    }
+
+    #[test]
+    fn expansion_is_capped() {
+        assert_eq!(expand("{a,b}{c,d}", 4).unwrap().len(), 4);
+        assert_eq!(
+            expand("{a,b}{c,d}", 3),
+            Err(ExpandError::TooLarge {
+                pattern: "{a,b}{c,d}".to_string(),
+                limit: 3,
+            })
+        );
+        // Huge ranges are rejected without being materialized
+        assert!(matches!(
+            expand("n{1..9999999999}", MAX_EXPANSION),
+            Err(ExpandError::TooLarge { .. })
+        ));
+    }
+}
@@ -0,0 +1,727 @@
+//! A compact local index of crate names.
+//!
+//! Checking names against crates.io takes one request each; checking them
+//! against a snapshot of every published name takes none. [`NameIndex`]
+//! holds such a snapshot, keyed by canonical name, in a small file: names are
+//! sorted and front-coded in blocks (each name stores only what differs from
+//! the one before it), the same way the key side of an FST shares prefixes.
+//! Exact lookups binary-search the block heads and scan one block; prefix
+//! queries scan from the first match; edit-distance queries reuse their
+//! dynamic-programming rows for the prefix each name shares with the last, so
+//! whole runs of names are ruled out at once.
+//!
+//! An index is built from any of the places crate names are published:
+//!
+//! - the `crates.csv` of a crates.io database dump (extracted from the
+//!   archive),
+//! - a checkout of the crates.io git index, or a snapshot of the sparse
+//!   index in the same directory layout,
+//! - a plain list with one name per line.
+//!
+//! Use it behind [`check_name`](crate::check::check_name) with
+//! [`Client::with_index`](crate::check::Client::with_index).
+
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+
+use crate::check::canon_crate_name;
+use crate::check::validation::validate_crate_name;
+use crate::config::cargo_home;
+
+/// Identifies an index file, and its format version.
+const MAGIC: &[u8; 8] = b"CAVIDX1\n";
+
+/// Names per block. Larger blocks compress better and look up more slowly.
+const BLOCK_SIZE: usize = 32;
+
+/// The file in the root of the crates.io index that is not a crate.
+const INDEX_CONFIG: &str = "config.json";
+
+/// Where `cargo avail index build` writes the index, and where `--offline`
+/// looks for it: `$CARGO_HOME/cargo-avail/names.idx`. `None` if there is no
+/// cargo home.
+#[must_use]
+pub fn default_path() -> Option<PathBuf> {
+    cargo_home().map(|home| home.join("cargo-avail").join("names.idx"))
+}
+
+/// Errors that can occur while building, writing or reading an index.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum IndexError {
+    /// A file could not be read or written.
+    Io(PathBuf, io::Error),
+    /// The file is not an index, or is damaged.
+    Corrupt(PathBuf),
+    /// The source holds no crate names, or is not in a known format.
+    Source(PathBuf, String),
+}
+
+impl fmt::Display for IndexError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(path, e) => write!(f, "reading {}: {e}", path.display()),
+            Self::Corrupt(path) => write!(f, "{}: not a name index", path.display()),
+            Self::Source(path, msg) => write!(f, "{}: {msg}", path.display()),
+        }
+    }
+}
+
+impl std::error::Error for IndexError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(_, e) => Some(e),
+            Self::Corrupt(_) | Self::Source(..) => None,
+        }
+    }
+}
+
+/// A crate name found by an index query.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct IndexMatch {
+    /// The name, spelled as published.
+    pub name: String,
+    /// Edit distance from the query; 0 for exact and prefix matches.
+    pub distance: usize,
+}
+
+/// A sorted, front-coded set of crate names keyed by canonical name.
+#[derive(Clone, PartialEq, Eq)]
+pub struct NameIndex {
+    len: usize,
+    /// The start of each block in `data`.
+    blocks: Vec<usize>,
+    data: Vec<u8>,
+}
+
+impl fmt::Debug for NameIndex {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NameIndex")
+            .field("len", &self.len)
+            .field("bytes", &self.data.len())
+            .finish_non_exhaustive()
+    }
+}
+
+/// One decoded entry: the canonical key and the published spelling.
+struct Entry {
+    /// Bytes shared with the previous key in the block.
+    shared: usize,
+    key: Vec<u8>,
+    spelling: Option<String>,
+}
+
+impl Entry {
+    fn name(&self) -> String {
+        self.spelling
+            .clone()
+            .unwrap_or_else(|| String::from_utf8_lossy(&self.key).into_owned())
+    }
+}
+
+impl NameIndex {
+    /// Build an index from published crate names. Names that fail crates.io
+    /// validation are skipped; of names that collide canonically, the first
+    /// spelling is kept.
+    ///
+    /// ```
+    /// use cargo_avail::index::NameIndex;
+    ///
+    /// let index = NameIndex::build(["serde_json", "tokio", "Serde-JSON"]);
+    /// assert_eq!(index.len(), 2);
+    /// assert_eq!(index.get("serde-json").as_deref(), Some("serde_json"));
+    /// assert_eq!(index.get("serde"), None);
+    /// ```
+    pub fn build<I, S>(names: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut entries: Vec<(String, String)> = names
+            .into_iter()
+            .map(|name| name.as_ref().trim().to_string())
+            .filter(|name| validate_crate_name(name).is_ok())
+            .map(|name| (canon_crate_name(&name), name))
+            .collect();
+        // Stable, so the first spelling of a key survives deduplication
+        entries.sort_by(|a, b| a.0.cmp(&b.0));
+        entries.dedup_by(|later, earlier| later.0 == earlier.0);
+
+        let mut blocks = Vec::new();
+        let mut data = Vec::new();
+        for (i, (key, spelling)) in entries.iter().enumerate() {
+            let shared = if i % BLOCK_SIZE == 0 {
+                blocks.push(data.len());
+                0
+            } else {
+                let previous = entries[i - 1].0.as_bytes();
+                previous
+                    .iter()
+                    .zip(key.as_bytes())
+                    .take_while(|(a, b)| a == b)
+                    .count()
+            };
+            write_varint(&mut data, shared);
+            write_bytes(&mut data, &key.as_bytes()[shared..]);
+            let spelling = if spelling == key { "" } else { spelling };
+            write_bytes(&mut data, spelling.as_bytes());
+        }
+        Self {
+            len: entries.len(),
+            blocks,
+            data,
+        }
+    }
+
+    /// How many names the index holds.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether the index holds no names.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The published spelling of the crate whose canonical name matches
+    /// `name`, if there is one.
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<String> {
+        let key = canon_crate_name(name);
+        let key = key.as_bytes();
+        // The last block whose head is not after the key
+        let block = self
+            .blocks
+            .partition_point(|&start| self.block_head(start).as_slice() <= key)
+            .checked_sub(1)?;
+        self.block_entries(block)
+            .find(|entry| entry.key.as_slice() >= key)
+            .filter(|entry| entry.key == key)
+            .map(|entry| entry.name())
+    }
+
+    /// Every name whose canonical form starts with the canonical form of
+    /// `prefix`, in canonical order.
+    ///
+    /// ```
+    /// use cargo_avail::index::NameIndex;
+    ///
+    /// let index = NameIndex::build(["acme-http", "acme_ws", "acmex", "other"]);
+    /// let names: Vec<String> = index.prefix("acme-").into_iter().map(|m| m.name).collect();
+    /// assert_eq!(names, ["acme-http", "acme_ws"]);
+    /// ```
+    #[must_use]
+    pub fn prefix(&self, prefix: &str) -> Vec<IndexMatch> {
+        let prefix = canon_crate_name(prefix);
+        let prefix = prefix.as_bytes();
+        let first = self
+            .blocks
+            .partition_point(|&start| self.block_head(start).as_slice() < prefix)
+            .saturating_sub(1);
+        (first..self.blocks.len())
+            .flat_map(|block| self.block_entries(block))
+            .skip_while(|entry| entry.key.as_slice() < prefix)
+            .take_while(|entry| entry.key.starts_with(prefix))
+            .map(|entry| IndexMatch {
+                name: entry.name(),
+                distance: 0,
+            })
+            .collect()
+    }
+
+    /// Every name within `max_distance` edits (insertions, deletions or
+    /// substitutions) of `name`, compared canonically, in canonical order.
+    ///
+    /// ```
+    /// use cargo_avail::index::NameIndex;
+    ///
+    /// let index = NameIndex::build(["serde", "serde_json", "sorde", "tokio"]);
+    /// let matches = index.fuzzy("serdy", 1);
+    /// assert_eq!(matches.iter().map(|m| m.name.as_str()).collect::<Vec<_>>(), ["serde"]);
+    /// assert_eq!(index.fuzzy("serdy", 2).len(), 2);
+    /// ```
+    #[must_use]
+    pub fn fuzzy(&self, name: &str, max_distance: usize) -> Vec<IndexMatch> {
+        let query = canon_crate_name(name).into_bytes();
+        // rows[i] is the edit-distance row after the first i bytes of the
+        // current key; rows for a shared prefix carry over to the next key
+        let mut rows: Vec<Vec<usize>> = vec![(0..=query.len()).collect()];
+        let mut matches = Vec::new();
+        for block in 0..self.blocks.len() {
+            for entry in self.block_entries(block) {
+                rows.truncate(entry.shared + 1);
+                // Stopped short of the shared prefix: every key under it is too far
+                if rows.len() <= entry.shared {
+                    continue;
+                }
+                for &byte in &entry.key[rows.len() - 1..] {
+                    let Some(previous) = rows.last() else {
+                        break;
+                    };
+                    let mut row = Vec::with_capacity(query.len() + 1);
+                    row.push(previous[0] + 1);
+                    for (j, &q) in query.iter().enumerate() {
+                        let substitution = previous[j] + usize::from(q != byte);
+                        row.push(substitution.min(previous[j + 1] + 1).min(row[j] + 1));
+                    }
+                    let hopeless = row.iter().all(|&d| d > max_distance);
+                    rows.push(row);
+                    if hopeless {
+                        break;
+                    }
+                }
+                if rows.len() == entry.key.len() + 1 {
+                    let distance = rows[entry.key.len()][query.len()];
+                    if distance <= max_distance {
+                        matches.push(IndexMatch {
+                            name: entry.name(),
+                            distance,
+                        });
+                    }
+                }
+            }
+        }
+        matches
+    }
+
+    /// Every name in the index, spelled as published, in canonical order.
+    pub fn names(&self) -> impl Iterator<Item = String> + '_ {
+        (0..self.blocks.len())
+            .flat_map(|block| self.block_entries(block))
+            .map(|entry| entry.name())
+    }
+
+    /// Serialize the index.
+    #[must_use]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = MAGIC.to_vec();
+        write_varint(&mut bytes, self.len);
+        write_varint(&mut bytes, self.blocks.len());
+        for &start in &self.blocks {
+            write_varint(&mut bytes, start);
+        }
+        bytes.extend_from_slice(&self.data);
+        bytes
+    }
+
+    /// Deserialize an index written by [`to_bytes`](Self::to_bytes), or
+    /// `None` if `bytes` are not one.
+    #[must_use]
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let mut rest = bytes.strip_prefix(MAGIC.as_slice())?;
+        let len = read_varint(&mut rest)?;
+        let block_count = read_varint(&mut rest)?;
+        let blocks = (0..block_count)
+            .map(|_| read_varint(&mut rest))
+            .collect::<Option<Vec<usize>>>()?;
+        let data = rest.to_vec();
+        let in_order = blocks.windows(2).all(|w| w[0] < w[1]);
+        let in_bounds = blocks.last().is_none_or(|&last| last < data.len());
+        if !in_order || !in_bounds || blocks.first().is_some_and(|&first| first != 0) {
+            return None;
+        }
+        let index = Self { len, blocks, data };
+        // Every entry must decode, and the count must match
+        let decoded = (0..index.blocks.len())
+            .map(|block| index.try_block_entries(block).map(|entries| entries.len()))
+            .sum::<Option<usize>>()?;
+        (decoded == len).then_some(index)
+    }
+
+    /// Read an index file.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`IndexError::Io`] if the file cannot be read, or
+    /// [`IndexError::Corrupt`] if it is not an index.
+    pub fn read(path: &Path) -> Result<Self, IndexError> {
+        let bytes = std::fs::read(path).map_err(|e| IndexError::Io(path.to_path_buf(), e))?;
+        Self::from_bytes(&bytes).ok_or_else(|| IndexError::Corrupt(path.to_path_buf()))
+    }
+
+    /// Write the index to a file, creating its directory if needed.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`IndexError::Io`] if the file cannot be written.
+    pub fn write(&self, path: &Path) -> Result<(), IndexError> {
+        let io_error = |e| IndexError::Io(path.to_path_buf(), e);
+        if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            std::fs::create_dir_all(dir).map_err(io_error)?;
+        }
+        std::fs::write(path, self.to_bytes()).map_err(io_error)
+    }
+
+    /// The full key at the head of the block starting at `start`.
+    fn block_head(&self, start: usize) -> Vec<u8> {
+        let mut rest = &self.data[start..];
+        decode_entry(&mut rest, &[])
+            .map(|e| e.key)
+            .unwrap_or_default()
+    }
+
+    /// The entries of a block, which [`from_bytes`](Self::from_bytes) has
+    /// checked decode cleanly.
+    fn block_entries(&self, block: usize) -> impl Iterator<Item = Entry> {
+        self.try_block_entries(block)
+            .unwrap_or_default()
+            .into_iter()
+    }
+
+    fn try_block_entries(&self, block: usize) -> Option<Vec<Entry>> {
+        let end = self
+            .blocks
+            .get(block + 1)
+            .copied()
+            .unwrap_or(self.data.len());
+        let mut rest = self.data.get(self.blocks[block]..end)?;
+        let mut entries: Vec<Entry> = Vec::new();
+        while !rest.is_empty() {
+            let previous = entries.last().map_or(&[][..], |e| e.key.as_slice());
+            entries.push(decode_entry(&mut rest, previous)?);
+        }
+        Some(entries)
+    }
+}
+
+fn decode_entry(rest: &mut &[u8], previous: &[u8]) -> Option<Entry> {
+    let shared = read_varint(rest)?;
+    let suffix = read_bytes(rest)?;
+    let spelling = read_bytes(rest)?;
+    let mut key = previous.get(..shared)?.to_vec();
+    key.extend_from_slice(suffix);
+    let spelling = if spelling.is_empty() {
+        None
+    } else {
+        Some(String::from_utf8(spelling.to_vec()).ok()?)
+    };
+    Some(Entry {
+        shared,
+        key,
+        spelling,
+    })
+}
+
+/// Append `n` as a LEB128 varint.
+fn write_varint(out: &mut Vec<u8>, mut n: usize) {
+    while n >= 0x80 {
+        // Truncation keeps the low seven bits, which is the point
+        out.push(u8::try_from(n & 0x7f).unwrap_or_default() | 0x80);
+        n >>= 7;
+    }
+    out.push(u8::try_from(n).unwrap_or_default());
+}
+
+fn read_varint(rest: &mut &[u8]) -> Option<usize> {
+    let mut n = 0usize;
+    for shift in (0..usize::BITS).step_by(7) {
+        let (&byte, tail) = rest.split_first()?;
+        *rest = tail;
+        n |= usize::from(byte & 0x7f).checked_shl(shift)?;
+        if byte & 0x80 == 0 {
+            return Some(n);
+        }
+    }
+    None
+}
+
+fn write_bytes(out: &mut Vec<u8>, bytes: &[u8]) {
+    write_varint(out, bytes.len());
+    out.extend_from_slice(bytes);
+}
+
+fn read_bytes<'a>(rest: &mut &'a [u8]) -> Option<&'a [u8]> {
+    let len = read_varint(rest)?;
+    if len > rest.len() {
+        return None;
+    }
+    let (bytes, tail) = rest.split_at(len);
+    *rest = tail;
+    Some(bytes)
+}
+
+/// Read the crate names published in `source`: a crates.io index directory
+/// (git checkout or sparse snapshot), the `crates.csv` of a database dump, or
+/// a list with one name per line.
+///
+/// # Errors
+///
+/// Returns [`IndexError::Io`] if the source cannot be read, or
+/// [`IndexError::Source`] if it holds no names.
+pub fn read_source(source: &Path) -> Result<Vec<String>, IndexError> {
+    let io_error = |e| IndexError::Io(source.to_path_buf(), e);
+    let names = if source.is_dir() {
+        let mut names = Vec::new();
+        walk_index_dir(source, &mut names)?;
+        names
+    } else {
+        let mut reader = BufReader::new(File::open(source).map_err(io_error)?);
+        let mut first = String::new();
+        reader.read_line(&mut first).map_err(io_error)?;
+        if first.contains(',') {
+            csv_names(source, &first, reader)?
+        } else {
+            let mut rest = String::new();
+            reader.read_to_string(&mut rest).map_err(io_error)?;
+            first
+                .lines()
+                .chain(rest.lines())
+                .map(str::trim)
+                .filter(|line| !line.is_empty() && !line.starts_with('#'))
+                .map(str::to_string)
+                .collect()
+        }
+    };
+    if names.is_empty() {
+        return Err(IndexError::Source(
+            source.to_path_buf(),
+            "no crate names found".to_string(),
+        ));
+    }
+    Ok(names)
+}
+
+/// Collect the crates in an index directory. Each crate is a file named
+/// after it; its first line records the published spelling.
+fn walk_index_dir(dir: &Path, names: &mut Vec<String>) -> Result<(), IndexError> {
+    let io_error = |e| IndexError::Io(dir.to_path_buf(), e);
+    for entry in std::fs::read_dir(dir).map_err(io_error)? {
+        let entry = entry.map_err(io_error)?;
+        let file_name = entry.file_name();
+        let Some(file_name) = file_name.to_str() else {
+            continue;
+        };
+        if file_name.starts_with('.') || file_name == INDEX_CONFIG {
+            continue;
+        }
+        let path = entry.path();
+        if entry.file_type().map_err(io_error)?.is_dir() {
+            walk_index_dir(&path, names)?;
+        } else if validate_crate_name(file_name).is_ok() {
+            names.push(published_spelling(&path).unwrap_or_else(|| file_name.to_string()));
+        }
+    }
+    Ok(())
+}
+
+/// The `name` recorded in the first line of an index file.
+fn published_spelling(path: &Path) -> Option<String> {
+    #[derive(serde::Deserialize)]
+    struct Version {
+        name: String,
+    }
+    let mut first = String::new();
+    BufReader::new(File::open(path).ok()?)
+        .read_line(&mut first)
+        .ok()?;
+    serde_json::from_str::<Version>(&first).ok().map(|v| v.name)
+}
+
+/// The `name` column of a CSV file whose header line is `header`. Quoted
+/// fields may hold commas, doubled quotes and line breaks, as descriptions
+/// and READMEs in a database dump do.
+fn csv_names(source: &Path, header: &str, reader: impl BufRead) -> Result<Vec<String>, IndexError> {
+    let column = header
+        .trim_end()
+        .split(',')
+        .position(|field| field.trim_matches('"') == "name")
+        .ok_or_else(|| IndexError::Source(source.to_path_buf(), "no `name` column".to_string()))?;
+    let mut names = Vec::new();
+    let mut field = String::new();
+    let (mut index, mut quoted, mut after_quote) = (0, false, false);
+    for byte in reader.bytes() {
+        let byte = byte.map_err(|e| IndexError::Io(source.to_path_buf(), e))?;
+        match (quoted, byte) {
+            (true, b'"') => (quoted, after_quote) = (false, true),
+            (false, b'"') => {
+                // A doubled quote inside a quoted field
+                if after_quote && index == column {
+                    field.push('"');
+                }
+                quoted = true;
+            }
+            (false, b',') => index += 1,
+            (false, b'\n') => {
+                if index >= column && !field.is_empty() {
+                    names.push(std::mem::take(&mut field));
+                }
+                index = 0;
+            }
+            (false, b'\r') => {}
+            _ => {
+                if index == column {
+                    field.push(char::from(byte));
+                }
+            }
+        }
+        if byte != b'"' {
+            after_quote = false;
+        }
+    }
+    if index >= column && !field.is_empty() {
+        names.push(field);
+    }
+    Ok(names)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(matches: &[IndexMatch]) -> Vec<&str> {
+        matches.iter().map(|m| m.name.as_str()).collect()
+    }
+
+    fn sample() -> NameIndex {
+        // Enough names for several blocks
+        let mut list: Vec<String> = (0..100).map(|i| format!("crate{i:03}")).collect();
+        list.extend(
+            [
+                "serde",
+                "serde_json",
+                "Serde-Yaml",
+                "tokio",
+                "tokio-util",
+                "a",
+            ]
+            .map(String::from),
+        );
+        NameIndex::build(list)
+    }
+
+    #[test]
+    fn exact_lookups_are_canonical() {
+        let index = sample();
+        assert_eq!(index.len(), 106);
+        assert_eq!(index.get("serde_yaml").as_deref(), Some("Serde-Yaml"));
+        assert_eq!(index.get("TOKIO").as_deref(), Some("tokio"));
+        assert_eq!(index.get("a").as_deref(), Some("a"));
+        assert_eq!(index.get("crate050").as_deref(), Some("crate050"));
+        assert_eq!(index.get("crate100"), None);
+        assert_eq!(index.get("0"), None);
+        assert_eq!(index.get("zzz"), None);
+    }
+
+    #[test]
+    fn invalid_and_duplicate_names_are_dropped() {
+        let index = NameIndex::build(["foo+bar", "", "foo-bar", "foo_bar", "FOO_BAR"]);
+        assert_eq!(index.names().collect::<Vec<_>>(), ["foo-bar"]);
+    }
+
+    #[test]
+    fn prefixes_span_blocks() {
+        let index = sample();
+        assert_eq!(index.prefix("crate0").len(), 100);
+        assert_eq!(names(&index.prefix("serde-")), ["serde_json", "Serde-Yaml"]);
+        assert!(index.prefix("zebra").is_empty());
+        assert_eq!(index.prefix("").len(), 106);
+    }
+
+    #[test]
+    fn fuzzy_matches_within_distance() {
+        let index = sample();
+        assert_eq!(names(&index.fuzzy("tokio", 0)), ["tokio"]);
+        let matches = index.fuzzy("tokyo", 1);
+        assert_eq!(names(&matches), ["tokio"]);
+        assert_eq!(matches[0].distance, 1);
+        assert_eq!(names(&index.fuzzy("serde-jsn", 1)), ["serde_json"]);
+    }
+
+    #[test]
+    fn fuzzy_agrees_with_a_full_scan() {
+        fn distance(a: &[u8], b: &[u8]) -> usize {
+            let mut row: Vec<usize> = (0..=b.len()).collect();
+            for (i, &x) in a.iter().enumerate() {
+                let mut next = vec![i + 1];
+                for (j, &y) in b.iter().enumerate() {
+                    next.push(
+                        (row[j] + usize::from(x != y))
+                            .min(row[j + 1] + 1)
+                            .min(next[j] + 1),
+                    );
+                }
+                row = next;
+            }
+            row[b.len()]
+        }
+        let index = sample();
+        for (query, max) in [
+            ("crate05", 1),
+            ("crat", 3),
+            ("serdejson", 2),
+            ("x", 1),
+            ("", 1),
+        ] {
+            let expected: Vec<String> = index
+                .names()
+                .filter(|name| distance(canon_crate_name(name).as_bytes(), query.as_bytes()) <= max)
+                .collect();
+            let found: Vec<String> = index
+                .fuzzy(query, max)
+                .into_iter()
+                .map(|m| m.name)
+                .collect();
+            assert_eq!(found, expected, "{query} within {max}");
+        }
+    }
+
+    #[test]
+    fn round_trips_through_bytes() {
+        let index = sample();
+        let bytes = index.to_bytes();
+        assert_eq!(NameIndex::from_bytes(&bytes), Some(index));
+        assert!(NameIndex::from_bytes(b"not an index").is_none());
+        assert!(NameIndex::from_bytes(&bytes[..bytes.len() - 3]).is_none());
+        let empty = NameIndex::build(Vec::<String>::new());
+        assert_eq!(NameIndex::from_bytes(&empty.to_bytes()), Some(empty));
+    }
+
+    #[test]
+    fn varints_round_trip() {
+        for n in [0, 1, 127, 128, 300, 1 << 20, usize::MAX] {
+            let mut bytes = Vec::new();
+            write_varint(&mut bytes, n);
+            assert_eq!(read_varint(&mut bytes.as_slice()), Some(n));
+        }
+    }
+
+    #[test]
+    fn csv_dumps_keep_the_name_column() {
+        let csv = "id,name,description\n\
+                   1,serde,\"A generic, fast \"\"framework\"\"\nover two lines\"\n\
+                   2,\"tokio\",runtime\r\n\
+                   3,anyhow,";
+        let mut reader = csv.as_bytes();
+        let mut header = String::new();
+        reader.read_line(&mut header).unwrap();
+        let names = csv_names(Path::new("crates.csv"), &header, reader).unwrap();
+        assert_eq!(names, ["serde", "tokio", "anyhow"]);
+        assert!(csv_names(Path::new("x.csv"), "id,title\n", "".as_bytes()).is_err());
+    }
+
+    #[test]
+    fn index_directories_are_walked() {
+        let dir = std::env::temp_dir().join("cargo-avail-test-index-dir");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(dir.join("se/rd")).unwrap();
+        std::fs::create_dir_all(dir.join("1")).unwrap();
+        std::fs::create_dir_all(dir.join(".git")).unwrap();
+        std::fs::write(dir.join("config.json"), "{}").unwrap();
+        std::fs::write(dir.join(".git/HEAD"), "").unwrap();
+        std::fs::write(
+            dir.join("se/rd/serde_json"),
+            "{\"name\":\"serde_json\",\"vers\":\"1.0.0\"}\n",
+        )
+        .unwrap();
+        std::fs::write(dir.join("se/rd/serde-yaml"), "{\"name\":\"Serde-Yaml\"}\n").unwrap();
+        std::fs::write(dir.join("1/a"), "").unwrap();
+        let mut found = read_source(&dir).unwrap();
+        found.sort();
+        assert_eq!(found, ["Serde-Yaml", "a", "serde_json"]);
+    }
+}
@@ -23,4 +23,26 @@
 //! }
 //! ```
 
+pub mod audit;
+pub mod binary;
 pub mod check;
+pub mod claim;
+pub mod classify;
+pub mod combine;
+pub mod config;
+pub mod credentials;
+pub mod distro;
+pub mod expand;
+pub mod index;
+pub mod lint;
+pub mod namespace;
+pub mod project;
+pub mod random;
+pub mod repair;
+mod rng;
+pub mod score;
+pub mod similar;
+pub mod subcommand;
+pub mod suggest;
+pub mod unicode;
+pub mod variants;
@@ -0,0 +1,371 @@
+//! Naming-convention lints for crate names that are valid but poor choices.
+//!
+//! crates.io accepts `My_Crate-Thing`, but mixed separators and uppercase
+//! letters make a crate harder to find, type and depend on. Lints are opt-in
+//! and configurable per lint with clippy-style levels: [`Level::Allow`]
+//! silences a lint, [`Level::Warn`] reports it next to the availability
+//! result, and [`Level::Deny`] additionally makes the name count as
+//! unavailable.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::check::canon_crate_name;
+
+/// Top-level modules of `std`. A crate with one of these names reads like a
+/// standard library path at every use site (`use sync::Mutex`).
+const STD_MODULES: &[&str] = &[
+    "any",
+    "arch",
+    "array",
+    "ascii",
+    "backtrace",
+    "borrow",
+    "boxed",
+    "cell",
+    "char",
+    "clone",
+    "cmp",
+    "convert",
+    "default",
+    "env",
+    "error",
+    "ffi",
+    "fmt",
+    "fs",
+    "future",
+    "hash",
+    "hint",
+    "io",
+    "iter",
+    "marker",
+    "mem",
+    "net",
+    "num",
+    "ops",
+    "option",
+    "os",
+    "panic",
+    "path",
+    "pin",
+    "prelude",
+    "primitive",
+    "process",
+    "ptr",
+    "rc",
+    "result",
+    "slice",
+    "str",
+    "string",
+    "sync",
+    "task",
+    "thread",
+    "time",
+    "vec",
+];
+
+/// Affixes that say "this is Rust", which every crate on crates.io already is
+/// (canonical form, so hyphens appear as underscores).
+const REDUNDANT_PREFIXES: &[&str] = &["rust_", "rs_"];
+const REDUNDANT_SUFFIXES: &[&str] = &["_rs", "_rust"];
+
+/// A naming-convention lint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[non_exhaustive]
+pub enum Lint {
+    /// The name uses both `-` and `_` as separators.
+    MixedSeparators,
+    /// The name contains uppercase letters.
+    Uppercase,
+    /// The name contains two separators in a row.
+    DoubledSeparator,
+    /// The name ends with a separator.
+    TrailingSeparator,
+    /// The name carries a redundant `-rs`, `-rust`, `rust-` or `rs-` affix.
+    RedundantAffix,
+    /// The name matches a top-level `std` module (`sync`, `io`, `fmt`).
+    ShadowsStd,
+}
+
+impl Lint {
+    /// Every lint, in reporting order.
+    pub const ALL: &'static [Self] = &[
+        Self::MixedSeparators,
+        Self::Uppercase,
+        Self::DoubledSeparator,
+        Self::TrailingSeparator,
+        Self::RedundantAffix,
+        Self::ShadowsStd,
+    ];
+
+    /// The lint's kebab-case name, as accepted on the command line.
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::MixedSeparators => "mixed-separators",
+            Self::Uppercase => "uppercase",
+            Self::DoubledSeparator => "doubled-separator",
+            Self::TrailingSeparator => "trailing-separator",
+            Self::RedundantAffix => "redundant-affix",
+            Self::ShadowsStd => "shadows-std",
+        }
+    }
+}
+
+impl fmt::Display for Lint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Lint {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|lint| lint.name() == s)
+            .ok_or_else(|| {
+                let known: Vec<&str> = Self::ALL.iter().map(|l| l.name()).collect();
+                format!("unknown lint `{s}` (expected one of: {})", known.join(", "))
+            })
+    }
+}
+
+/// How a lint finding is treated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[non_exhaustive]
+pub enum Level {
+    /// The lint is not checked.
+    Allow,
+    /// Findings are reported next to the availability result.
+    Warn,
+    /// Findings are reported and the name counts as unavailable.
+    Deny,
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Allow => write!(f, "allow"),
+            Self::Warn => write!(f, "warn"),
+            Self::Deny => write!(f, "deny"),
+        }
+    }
+}
+
+/// Per-lint levels. Every lint defaults to [`Level::Warn`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LintConfig {
+    levels: HashMap<Lint, Level>,
+}
+
+impl LintConfig {
+    /// A configuration with every lint at [`Level::Warn`].
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            levels: Lint::ALL.iter().map(|&lint| (lint, Level::Warn)).collect(),
+        }
+    }
+
+    /// Set the level of a single lint.
+    pub fn set(&mut self, lint: Lint, level: Level) -> &mut Self {
+        self.levels.insert(lint, level);
+        self
+    }
+
+    /// The configured level of a lint.
+    #[must_use]
+    pub fn level(&self, lint: Lint) -> Level {
+        self.levels.get(&lint).copied().unwrap_or(Level::Warn)
+    }
+
+    /// Promote every [`Level::Warn`] lint to [`Level::Deny`], like clippy's
+    /// `-D warnings`.
+    pub fn deny_warnings(&mut self) -> &mut Self {
+        for level in self.levels.values_mut() {
+            if *level == Level::Warn {
+                *level = Level::Deny;
+            }
+        }
+        self
+    }
+}
+
+impl Default for LintConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A lint that fired on a name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Finding {
+    /// The lint that fired.
+    pub lint: Lint,
+    /// The level it fired at ([`Level::Warn`] or [`Level::Deny`]).
+    pub level: Level,
+    /// A human-readable explanation.
+    pub message: String,
+}
+
+impl fmt::Display for Finding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}[{}]: {}", self.level, self.lint, self.message)
+    }
+}
+
+/// Run every non-allowed lint against a name.
+///
+/// Lints look only at the spelling of the name, so they are meaningful for
+/// names that pass crates.io validation.
+///
+/// ```
+/// use cargo_avail::lint::{lint_name, Lint, LintConfig};
+///
+/// let findings = lint_name("My_Crate-Thing", &LintConfig::new());
+/// let lints: Vec<Lint> = findings.iter().map(|f| f.lint).collect();
+/// assert_eq!(lints, [Lint::MixedSeparators, Lint::Uppercase]);
+/// assert!(lint_name("my-crate", &LintConfig::new()).is_empty());
+/// ```
+#[must_use]
+pub fn lint_name(name: &str, config: &LintConfig) -> Vec<Finding> {
+    Lint::ALL
+        .iter()
+        .copied()
+        .filter(|&lint| config.level(lint) != Level::Allow)
+        .filter_map(|lint| {
+            check(lint, name).map(|message| Finding {
+                lint,
+                level: config.level(lint),
+                message,
+            })
+        })
+        .collect()
+}
+
+/// Whether any finding is at [`Level::Deny`].
+#[must_use]
+pub fn any_denied(findings: &[Finding]) -> bool {
+    findings.iter().any(|f| f.level == Level::Deny)
+}
+
+fn check(lint: Lint, name: &str) -> Option<String> {
+    let canonical = canon_crate_name(name);
+    match lint {
+        Lint::MixedSeparators => (name.contains('-') && name.contains('_'))
+            .then(|| "mixes `-` and `_` separators".to_string()),
+        Lint::Uppercase => name.contains(|c: char| c.is_ascii_uppercase()).then(|| {
+            "contains uppercase letters; crate names are conventionally lowercase".to_string()
+        }),
+        Lint::DoubledSeparator => ["--", "__", "-_", "_-"]
+            .iter()
+            .find(|pair| name.contains(*pair))
+            .map(|pair| format!("contains a doubled separator (`{pair}`)")),
+        Lint::TrailingSeparator => name
+            .ends_with(['-', '_'])
+            .then(|| "ends with a separator".to_string()),
+        Lint::RedundantAffix => REDUNDANT_PREFIXES
+            .iter()
+            .find(|p| canonical.starts_with(*p) && canonical.len() > p.len())
+            .map(|p| format!("`{}` prefix is redundant on crates.io", p.replace('_', "-")))
+            .or_else(|| {
+                REDUNDANT_SUFFIXES
+                    .iter()
+                    .find(|s| canonical.ends_with(*s) && canonical.len() > s.len())
+                    .map(|s| format!("`{}` suffix is redundant on crates.io", s.replace('_', "-")))
+            }),
+        Lint::ShadowsStd => STD_MODULES
+            .contains(&canonical.as_str())
+            .then(|| format!("shadows the `std::{canonical}` module path")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lints(name: &str) -> Vec<Lint> {
+        lint_name(name, &LintConfig::new())
+            .into_iter()
+            .map(|f| f.lint)
+            .collect()
+    }
+
+    #[test]
+    fn conventional_names_are_clean() {
+        assert!(lints("serde_json").is_empty());
+        assert!(lints("tokio-util").is_empty());
+        assert!(lints("rustls").is_empty());
+    }
+
+    #[test]
+    fn separator_lints() {
+        assert_eq!(lints("foo-bar_baz"), [Lint::MixedSeparators]);
+        assert_eq!(lints("foo--bar"), [Lint::DoubledSeparator]);
+        assert_eq!(lints("foo_"), [Lint::TrailingSeparator]);
+    }
+
+    #[test]
+    fn redundant_affixes() {
+        assert_eq!(lints("serde-rs"), [Lint::RedundantAffix]);
+        assert_eq!(lints("rust-serde"), [Lint::RedundantAffix]);
+        assert_eq!(lints("foo_rust"), [Lint::RedundantAffix]);
+        // A bare affix is not an affix
+        assert!(lints("rust").is_empty());
+    }
+
+    #[test]
+    fn std_module_names() {
+        assert_eq!(lints("sync"), [Lint::ShadowsStd]);
+        assert_eq!(lints("IO"), [Lint::Uppercase, Lint::ShadowsStd]);
+        assert!(lints("syncx").is_empty());
+    }
+
+    #[test]
+    fn allowed_lints_are_skipped() {
+        let mut config = LintConfig::new();
+        config.set(Lint::Uppercase, Level::Allow);
+        let findings = lint_name("My_Crate-Thing", &config);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].lint, Lint::MixedSeparators);
+        assert!(!any_denied(&findings));
+    }
+
+    #[test]
+    fn deny_warnings_promotes_warn_only() {
+        let mut config = LintConfig::new();
+        config.set(Lint::Uppercase, Level::Allow).deny_warnings();
+        assert_eq!(config.level(Lint::Uppercase), Level::Allow);
+        assert_eq!(config.level(Lint::ShadowsStd), Level::Deny);
+        assert!(any_denied(&lint_name("io", &config)));
+    }
+
+    #[test]
+    fn each_lint_has_its_own_level() {
+        for &lint in Lint::ALL {
+            let mut config = LintConfig::new();
+            config.set(lint, Level::Deny);
+            for &other in Lint::ALL {
+                let expected = if other == lint {
+                    Level::Deny
+                } else {
+                    Level::Warn
+                };
+                assert_eq!(config.level(other), expected, "{lint} vs {other}");
+            }
+        }
+    }
+
+    #[test]
+    fn lint_names_round_trip() {
+        for &lint in Lint::ALL {
+            assert_eq!(lint.name().parse::<Lint>(), Ok(lint));
+        }
+        assert!("no-such-lint".parse::<Lint>().is_err());
+    }
+}
@@ -1,67 +1,1837 @@
+use std::collections::HashMap;
+use std::ffi::OsString;
 use std::fmt::Write as _;
-use std::io::{self, BufRead, IsTerminal};
+use std::io::{self, BufRead, IsTerminal, Write as _};
+use std::path::{Path, PathBuf};
 use std::process::ExitCode;
+use std::str::FromStr;
+use std::time::Duration;
 
-use clap::Parser;
+use clap::{Args, Parser, Subcommand};
 use serde::Serialize;
 
+use cargo_avail::audit::{Drift, ExpectedOwners};
+use cargo_avail::binary::{
+    BinaryClash, BinaryWarning, Installs, binary_clashes, manifest_binaries,
+};
 use cargo_avail::check::{
-    Availability, CheckError, Client, MAX_CONCURRENT_REQUESTS, canon_crate_name, check_name,
+    Availability, CheckError, Client, CrateDetails, MAX_CONCURRENT_REQUESTS, Owner, Report,
+    canon_crate_name, check_name_report, crate_owners, reverse_dependency_count,
 };
+use cargo_avail::claim::{ClaimError, ClaimRequest, DraftFormat, HELP_ADDRESS};
+use cargo_avail::classify::{Classification, ClassifyOptions, Label, classify};
+use cargo_avail::combine::{Combinations, parse_wordlist};
+use cargo_avail::credentials::cargo_token;
+use cargo_avail::distro::{Distro, DistroIssue, PackageList, distro_issues};
+use cargo_avail::expand::{MAX_EXPANSION, expand};
+use cargo_avail::index::{self, IndexError, IndexMatch, NameIndex};
+use cargo_avail::lint::{Finding, Level, Lint, LintConfig, any_denied, lint_name};
+use cargo_avail::namespace::{Namespace, related_crates};
+use cargo_avail::project::{DEFAULT_TERMS, Project};
+use cargo_avail::random::{DEFAULT_LENGTHS, Lengths, NameModel};
+use cargo_avail::repair::suggest_fix;
+use cargo_avail::score::{Score, score};
+use cargo_avail::similar::find_similar;
+use cargo_avail::subcommand::{PLUGIN_PREFIX, cargo_aliases, subcommand_conflicts};
+use cargo_avail::suggest::{Candidate, Origin, SuggestOptions, candidates};
+use cargo_avail::unicode::check_lookalikes;
+use cargo_avail::variants::{Affix, DEFAULT_FAMILY_MEMBERS, family, variants};
+
+#[derive(Serialize)]
+struct JsonResult<'a> {
+    name: String,
+    status: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    collides_with: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    collision: Option<String>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    yanked_only: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rdeps: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    entrenched: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    suggestion: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    fixed_from: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pattern: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    fix: Option<JsonAlternative>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    variants: Vec<JsonAlternative>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    similar_to: Option<JsonSimilar>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    subcommand_conflicts: Vec<JsonConflict>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    binary_clashes: Vec<JsonBinaryClash>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    distro_packages: Vec<JsonDistroPackage>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    distro_issues: Vec<JsonDistroIssue>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    score: Option<Score>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    related: Option<&'a Namespace>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    lints: Vec<JsonLint>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    details: Option<&'a CrateDetails>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    classification: Option<Classification>,
+}
+
+#[derive(Serialize)]
+struct JsonLint {
+    lint: String,
+    level: String,
+    message: String,
+}
+
+#[derive(Serialize)]
+struct JsonSimilar {
+    name: String,
+    resemblance: String,
+}
+
+#[derive(Serialize)]
+struct JsonConflict {
+    kind: String,
+    command: String,
+    message: String,
+}
+
+#[derive(Serialize)]
+struct JsonBinaryClash {
+    binary: String,
+    kind: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    path: Option<PathBuf>,
+    message: String,
+}
+
+#[derive(Serialize)]
+struct JsonDistroPackage {
+    distro: String,
+    package: String,
+}
+
+#[derive(Serialize)]
+struct JsonDistroIssue {
+    distro: String,
+    kind: String,
+    package: String,
+    message: String,
+}
+
+#[derive(Serialize)]
+struct JsonAlternative {
+    name: String,
+    status: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+#[derive(Serialize)]
+struct JsonOwners<'a> {
+    name: String,
+    status: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    owners: Option<&'a [Owner]>,
+    #[serde(skip_serializing_if = "<[_]>::is_empty")]
+    drift: &'a [Drift],
+}
+
+#[derive(Serialize)]
+struct JsonFamily {
+    family: String,
+    claimable: bool,
+    members: Vec<JsonAlternative>,
+    blocked: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct JsonIndexMatch<'a> {
+    query: &'a str,
+    name: String,
+    distance: usize,
+}
+
+#[derive(Serialize)]
+struct JsonSuggestion<'a> {
+    name: &'a str,
+    status: String,
+    score: Score,
+    origin: Origin,
+}
+
+/// Generated candidates checked at a time by `suggest`, `random` and
+/// `combine`. Most candidates are speculative, so they are checked in small
+/// rounds rather than all at once.
+const CANDIDATE_BATCH: usize = 10;
+
+/// Pause between rounds of generated candidates, to stay well inside the
+/// crates.io rate limit.
+const CANDIDATE_PAUSE: Duration = Duration::from_secs(1);
+
+/// Options that need crates.io metadata a local index does not have.
+const ONLINE_ONLY: [&str; 5] = [
+    "crowded",
+    "rdeps",
+    "entrenched",
+    "details",
+    "classify_taken",
+];
+
+/// The order results are printed in.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum SortOrder {
+    /// As given.
+    Input,
+    /// Available names first, best scored first.
+    Score,
+}
+
+impl FromStr for SortOrder {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "input" => Ok(Self::Input),
+            "score" => Ok(Self::Score),
+            _ => Err(format!(
+                "unknown order `{s}` (expected one of: input, score)"
+            )),
+        }
+    }
+}
+
+#[derive(Parser)]
+#[command(
+    name = "cargo-avail",
+    bin_name = "cargo avail",
+    version,
+    about = "Check whether crate names are truly available on crates.io",
+    after_help = "Checks name validity (character rules, length), reserved names \
+                  (std, core, alloc, nul, com0, etc.), and the crates.io API \
+                  with canonical matching (hyphens and underscores are equivalent).\n\n\
+                  Cannot detect recently deleted crates (requires DB access). \
+                  A name passing all checks could still fail at publish time.\n\n\
+                  To check a name that matches a subcommand, pass it after `--`.",
+    args_conflicts_with_subcommands = true
+)]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    /// Crate names to check (also reads from stdin)
+    names: Vec<String>,
+
+    /// Suppress output, exit code only
+    #[arg(short, long, conflicts_with = "json")]
+    quiet: bool,
+
+    /// Only print available names
+    #[arg(short, long, conflicts_with = "json")]
+    available_only: bool,
+
+    /// Output results as NDJSON (one JSON object per line)
+    #[arg(long)]
+    json: bool,
+
+    /// Expand brace lists and ranges in names: acme-{http,ws}-{client,server}, foo{1..3}
+    #[arg(long)]
+    expand: bool,
+
+    /// Most names a single pattern may expand to
+    #[arg(
+        long,
+        value_name = "N",
+        default_value_t = MAX_EXPANSION,
+        requires = "expand"
+    )]
+    max_expansion: usize,
+
+    /// Check the ASCII transliteration of names containing lookalike characters
+    #[arg(long)]
+    fix: bool,
+
+    /// Propose the closest valid name for invalid names and check it too
+    #[arg(long)]
+    suggest_fix: bool,
+
+    /// Also check common affix variants of each name (foo-rs, rust-foo, foo-cli, ...)
+    #[arg(long)]
+    variants: bool,
+
+    /// Affix to try instead of the defaults, e.g. -rs or rust- (repeatable, implies --variants)
+    #[arg(
+        long = "affix",
+        value_name = "AFFIX",
+        value_delimiter = ',',
+        allow_hyphen_values = true
+    )]
+    affixes: Vec<Affix>,
+
+    /// Print results in input order, or available names first by quality score
+    #[arg(long, value_name = "ORDER", default_value = "input")]
+    sort: SortOrder,
+
+    /// Only print the N best-scored available names (implies --sort score)
+    #[arg(long, value_name = "N")]
+    top: Option<usize>,
+
+    /// Don't warn about available names that resemble popular crates
+    #[arg(long)]
+    no_similar: bool,
+
+    /// Don't warn about cargo-* names whose command clashes with a cargo command, alias or plugin
+    #[arg(long)]
+    no_subcommand_check: bool,
+
+    /// Warn when a name would shadow an executable on PATH, a common Unix tool or a rustup tool
+    #[arg(long)]
+    bin_check: bool,
+
+    /// Check the [[bin]] names of this Cargo.toml for its package (implies --bin-check)
+    #[arg(long, value_name = "PATH")]
+    manifest_path: Option<PathBuf>,
+
+    /// Check the rust-<name> packaging name in these distributions: debian, fedora
+    #[arg(long = "distro", value_name = "DISTRO", value_delimiter = ',')]
+    distros: Vec<Distro>,
+
+    /// File of existing distribution packages to check packaging names against (repeatable)
+    #[arg(long = "distro-packages", value_name = "FILE", requires = "distros")]
+    distro_packages: Vec<PathBuf>,
+
+    /// Search crates.io for existing crates sharing each available name's stem
+    #[arg(long)]
+    crowded: bool,
+
+    #[command(flatten)]
+    owners: OwnerArgs,
+
+    /// Answer from the local name index instead of crates.io (see `cargo avail index build`)
+    #[arg(long, conflicts_with_all = ONLINE_ONLY)]
+    offline: bool,
+
+    /// Answer from this name index instead of crates.io (implies --offline)
+    #[arg(long, value_name = "FILE", conflicts_with_all = ONLINE_ONLY)]
+    index: Option<PathBuf>,
+
+    /// Show metadata about taken crates (dates, versions, downloads, links)
+    #[arg(long)]
+    details: bool,
+
+    /// Label taken crates as active, dormant or likely-placeholder
+    #[arg(long)]
+    classify_taken: bool,
+
+    /// Years without an update before a taken crate counts as dormant
+    #[arg(
+        long,
+        value_name = "YEARS",
+        default_value_t = 2,
+        requires = "classify_taken"
+    )]
+    dormant_years: u32,
+
+    /// Count the crates that depend on each taken name
+    #[arg(long)]
+    rdeps: bool,
+
+    /// Mark taken names with at least N reverse dependencies as entrenched (implies --rdeps)
+    #[arg(long, value_name = "N")]
+    entrenched: Option<u64>,
+
+    /// Lint names for naming conventions (separators, case, affixes, std paths)
+    #[arg(long)]
+    lint: bool,
+
+    /// Silence a lint (implies --lint)
+    #[arg(short = 'A', long = "allow", value_name = "LINT")]
+    allow_lints: Vec<Lint>,
+
+    /// Report a lint as a warning (implies --lint)
+    #[arg(short = 'W', long = "warn", value_name = "LINT")]
+    warn_lints: Vec<Lint>,
+
+    /// Report a lint and count the name as unavailable (implies --lint)
+    #[arg(short = 'D', long = "deny", value_name = "LINT")]
+    deny_lints: Vec<Lint>,
+
+    /// Count names with any lint warning as unavailable (implies --lint)
+    #[arg(long = "deny-lints")]
+    deny_all_lints: bool,
+}
+
+/// Who counts as "you" when a taken name is owned.
+#[derive(Args)]
+struct OwnerArgs {
+    /// Report names you own as `owned` using your cargo crates.io token
+    #[arg(long)]
+    owned: bool,
+
+    /// Report names owned by this crates.io login as `owned`, instead of the token's user (repeatable)
+    #[arg(long = "owner", value_name = "LOGIN")]
+    logins: Vec<String>,
+
+    /// Also treat crates owned by this team as yours, e.g. github:org:team (repeatable)
+    #[arg(long = "owner-team", value_name = "TEAM")]
+    teams: Vec<String>,
+}
+
+impl OwnerArgs {
+    /// `client`, reporting names these owners own as `owned`. With `--owned`
+    /// and no `--owner`, the token cargo would use identifies you.
+    fn apply(&self, mut client: Client) -> Result<Client, String> {
+        if self.owned && self.logins.is_empty() {
+            match cargo_token() {
+                Ok(Some(token)) => client = client.with_token(token),
+                Ok(None) => {
+                    return Err("no crates.io token found (run `cargo login`, set \
+                                CARGO_REGISTRY_TOKEN or pass --owner)"
+                        .to_string());
+                }
+                Err(e) => return Err(format!("loading crates.io token: {e}")),
+            }
+        }
+        if self.logins.is_empty() && self.teams.is_empty() {
+            return Ok(client);
+        }
+        Ok(client.with_owners(self.logins.iter().chain(&self.teams).cloned()))
+    }
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Audit the owners of existing crates against an expected-owners file
+    Owners(OwnersArgs),
+    /// Draft a crates.io name-transfer request for a taken crate (sends nothing)
+    ClaimRequest(ClaimArgs),
+    /// Check that a base name and all of its member crates can be claimed together
+    Family(FamilyArgs),
+    /// Suggest available names built from keywords describing the crate
+    Suggest(SuggestArgs),
+    /// Check names combining one word from each wordlist file (exits 0 if any is available)
+    Combine(CombineArgs),
+    /// Generate pronounceable random names until enough are available
+    Random(RandomArgs),
+    /// Build or query a local index of published crate names
+    #[command(subcommand)]
+    Index(IndexCommand),
+}
+
+#[derive(Subcommand)]
+enum IndexCommand {
+    /// Compile crate names from a db-dump crates.csv, an index checkout or a name list
+    Build(IndexBuildArgs),
+    /// Look names up in the index: exactly, by prefix (acme-*) or by edit distance
+    Query(IndexQueryArgs),
+}
+
+#[derive(Args)]
+struct IndexBuildArgs {
+    /// A crates.csv from a crates.io database dump, a git or sparse index directory, or a file of names
+    source: PathBuf,
+
+    /// Where to write the index [default: $CARGO_HOME/cargo-avail/names.idx]
+    #[arg(short, long, value_name = "FILE")]
+    output: Option<PathBuf>,
+
+    /// Suppress output, exit code only
+    #[arg(short, long)]
+    quiet: bool,
+}
+
+#[derive(Args)]
+struct IndexQueryArgs {
+    /// Names to look up; a trailing `*` matches every name with that prefix
+    #[arg(required = true)]
+    queries: Vec<String>,
+
+    /// Match names within N edits (insertions, deletions, substitutions); not for prefix queries
+    #[arg(short, long, value_name = "N")]
+    distance: Option<usize>,
+
+    /// The index to query [default: $CARGO_HOME/cargo-avail/names.idx]
+    #[arg(long, value_name = "FILE")]
+    index: Option<PathBuf>,
+
+    /// Suppress output, exit code only
+    #[arg(short, long, conflicts_with = "json")]
+    quiet: bool,
+
+    /// Output matches as NDJSON (one JSON object per line)
+    #[arg(long)]
+    json: bool,
+}
+
+#[derive(Args)]
+struct OwnersArgs {
+    /// Crate names to audit (also reads from stdin)
+    names: Vec<String>,
+
+    /// TOML file listing `required`, `allowed` and `departed` owner logins
+    #[arg(long, value_name = "FILE")]
+    expected: Option<PathBuf>,
+
+    /// Suppress output, exit code only
+    #[arg(short, long, conflicts_with = "json")]
+    quiet: bool,
+
+    /// Output results as NDJSON (one JSON object per line)
+    #[arg(long)]
+    json: bool,
+}
+
+#[derive(Args)]
+struct ClaimArgs {
+    /// The taken crate name to request
+    name: String,
+
+    /// Draft format: markdown or text
+    #[arg(long, value_name = "FORMAT", default_value = "markdown")]
+    format: DraftFormat,
+
+    /// Years without an update before the crate counts as dormant
+    #[arg(long, value_name = "YEARS", default_value_t = 2)]
+    dormant_years: u32,
+
+    /// Send a HEAD request to the crate's repository URL to see whether it still exists
+    #[arg(long)]
+    check_repository: bool,
+}
+
+#[derive(Args)]
+struct FamilyArgs {
+    /// The project's base crate name
+    base: String,
+
+    /// Member suffixes checked as <BASE>-<MEMBER> [default: core,macros,derive,cli]
+    #[arg(long, value_name = "MEMBERS", value_delimiter = ',')]
+    members: Vec<String>,
+
+    #[command(flatten)]
+    owners: OwnerArgs,
+
+    /// Suppress output, exit code only
+    #[arg(short, long, conflicts_with = "json")]
+    quiet: bool,
+
+    /// Output the family as a single JSON object
+    #[arg(long)]
+    json: bool,
+}
+
+#[derive(Args)]
+struct SuggestArgs {
+    /// Keywords describing the crate
+    #[arg(required_unless_present = "from_project")]
+    keywords: Vec<String>,
+
+    /// Also take keywords from the project in DIR: its Cargo.toml
+    /// description and keywords, and its README headings
+    #[arg(long, value_name = "DIR")]
+    from_project: Option<PathBuf>,
+
+    /// How many keywords to take from the project
+    #[arg(
+        long,
+        value_name = "N",
+        default_value_t = DEFAULT_TERMS,
+        requires = "from_project"
+    )]
+    terms: usize,
+
+    /// How many available names to print
+    #[arg(short = 'n', long, value_name = "N", default_value_t = 10)]
+    count: usize,
+
+    /// Separators to join keywords with; "" joins them directly (repeatable) [default: -,""]
+    #[arg(
+        long = "separator",
+        value_name = "SEP",
+        value_delimiter = ',',
+        allow_hyphen_values = true
+    )]
+    separators: Vec<String>,
+
+    /// Affix to try on each keyword, e.g. -rs or rust- (repeatable) [default: as --variants]
+    #[arg(
+        long = "affix",
+        value_name = "AFFIX",
+        value_delimiter = ',',
+        allow_hyphen_values = true
+    )]
+    affixes: Vec<Affix>,
+
+    /// Don't try affixes
+    #[arg(long, conflicts_with = "affixes")]
+    no_affixes: bool,
+
+    /// Don't shorten keywords to their first syllable (parser -> parjson)
+    #[arg(long)]
+    no_truncations: bool,
+
+    /// Don't blend pairs of keywords into one word (parser + json -> paron)
+    #[arg(long)]
+    no_blends: bool,
+
+    /// Check at most N candidates against crates.io
+    #[arg(long, value_name = "N", default_value_t = 100)]
+    max_checks: usize,
+
+    /// Suppress output, exit code only
+    #[arg(short, long, conflicts_with = "json")]
+    quiet: bool,
+
+    /// Output results as NDJSON (one JSON object per line)
+    #[arg(long)]
+    json: bool,
+}
+
+#[derive(Args)]
+struct CombineArgs {
+    /// Wordlist files, one word per line (`#` starts a comment)
+    #[arg(required = true, value_name = "FILE")]
+    files: Vec<PathBuf>,
+
+    /// Separator between words
+    #[arg(
+        long,
+        value_name = "SEP",
+        default_value = "-",
+        allow_hyphen_values = true
+    )]
+    sep: String,
+
+    /// Skip names longer than N characters
+    #[arg(long, value_name = "N")]
+    max_len: Option<usize>,
+
+    /// Check a random sample of N names instead of every combination
+    #[arg(long, value_name = "N")]
+    sample: Option<usize>,
+
+    /// Seed for --sample; the same seed always picks the same names
+    #[arg(long, value_name = "SEED", default_value_t = 0, requires = "sample")]
+    seed: u64,
+
+    /// Check at most N names of the full product against crates.io
+    #[arg(
+        long,
+        value_name = "N",
+        default_value_t = 100,
+        conflicts_with = "sample"
+    )]
+    max_checks: usize,
+
+    /// Suppress output, exit code only
+    #[arg(short, long, conflicts_with = "json")]
+    quiet: bool,
+
+    /// Only print available names
+    #[arg(short, long, conflicts_with = "json")]
+    available_only: bool,
+
+    /// Output results as NDJSON (one JSON object per line)
+    #[arg(long)]
+    json: bool,
+}
+
+#[derive(Args)]
+struct RandomArgs {
+    /// How many available names to print
+    #[arg(short = 'n', long, value_name = "N", default_value_t = 10)]
+    count: usize,
+
+    /// Name lengths, e.g. 5..8 or 6
+    #[arg(long, value_name = "MIN..MAX", default_value_t = DEFAULT_LENGTHS)]
+    len: Lengths,
+
+    /// Seed for a reproducible sequence of names [default: random]
+    #[arg(long, value_name = "SEED")]
+    seed: Option<u64>,
+
+    /// Check at most N candidates against crates.io
+    #[arg(long, value_name = "N", default_value_t = 100)]
+    max_checks: usize,
+
+    /// Suppress output, exit code only
+    #[arg(short, long, conflicts_with = "json")]
+    quiet: bool,
+
+    /// Output results as NDJSON (one JSON object per line)
+    #[arg(long)]
+    json: bool,
+}
+
+impl SuggestArgs {
+    /// The candidate generation options.
+    fn options(&self) -> SuggestOptions {
+        let defaults = SuggestOptions::default();
+        SuggestOptions {
+            separators: if self.separators.is_empty() {
+                defaults.separators
+            } else {
+                self.separators.clone()
+            },
+            affixes: if self.no_affixes {
+                Vec::new()
+            } else if self.affixes.is_empty() {
+                defaults.affixes
+            } else {
+                self.affixes.clone()
+            },
+            truncations: !self.no_truncations,
+            blends: !self.no_blends,
+        }
+    }
+}
+
+impl Cli {
+    /// The crates.io client, reading the local index with `--offline` or
+    /// `--index` and authenticated when `--owned` asks for your token.
+    fn client(&self) -> Result<Client, String> {
+        let mut client = Client::new();
+        if self.offline || self.index.is_some() {
+            let index = read_index(self.index.as_deref())?;
+            client = client.with_index(index);
+        }
+        self.owners.apply(client)
+    }
+
+    /// The affixes to expand names with, or `None` if variants were not requested.
+    fn affixes(&self) -> Option<Vec<Affix>> {
+        if !self.variants && self.affixes.is_empty() {
+            return None;
+        }
+        Some(if self.affixes.is_empty() {
+            Affix::defaults()
+        } else {
+            self.affixes.clone()
+        })
+    }
+
+    /// The lint configuration, or `None` if linting was not requested.
+    fn lint_config(&self) -> Option<LintConfig> {
+        let enabled = self.lint
+            || self.deny_all_lints
+            || !self.allow_lints.is_empty()
+            || !self.warn_lints.is_empty()
+            || !self.deny_lints.is_empty();
+        if !enabled {
+            return None;
+        }
+        let mut config = LintConfig::new();
+        for (lints, level) in [
+            (&self.allow_lints, Level::Allow),
+            (&self.warn_lints, Level::Warn),
+            (&self.deny_lints, Level::Deny),
+        ] {
+            for &lint in lints {
+                config.set(lint, level);
+            }
+        }
+        if self.deny_all_lints {
+            config.deny_warnings();
+        }
+        Some(config)
+    }
+}
+
+/// Sanitize a string for tab-separated output: replace control chars with escape sequences.
+fn sanitize(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for ch in s.chars() {
+        match ch {
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            '\0' => out.push_str("\\0"),
+            c if c.is_control() => {
+                let _ = write!(out, "\\x{:02x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+type CheckResult = Result<Report, CheckError>;
+
+/// Check names in parallel, capped at `MAX_CONCURRENT_REQUESTS` threads.
+fn check_all(client: &Client, names: &[String]) -> Vec<(String, CheckResult)> {
+    run_all(names, |name| check_name_report(client, name))
+}
+
+/// Run a crates.io query for each name in parallel, capped at
+/// `MAX_CONCURRENT_REQUESTS` threads, preserving input order.
+fn run_all<T, F>(names: &[String], query: F) -> Vec<(String, Result<T, CheckError>)>
+where
+    T: Send,
+    F: Fn(&str) -> Result<T, CheckError> + Sync,
+{
+    let query = &query;
+    let mut results = Vec::with_capacity(names.len());
+    for chunk in names.chunks(MAX_CONCURRENT_REQUESTS) {
+        let chunk_results: Vec<_> = std::thread::scope(|s| {
+            let handles: Vec<_> = chunk
+                .iter()
+                .map(|name| s.spawn(move || (name.clone(), query(name))))
+                .collect();
+            handles
+                .into_iter()
+                .zip(chunk)
+                .map(|(h, original_name)| {
+                    h.join().unwrap_or_else(|_| {
+                        (
+                            original_name.clone(),
+                            Err(CheckError::Internal("thread panic".into())),
+                        )
+                    })
+                })
+                .collect()
+        });
+        results.extend(chunk_results);
+    }
+    results
+}
+
+/// Render a check result as the status column of the text output.
+fn status_string(result: &CheckResult) -> String {
+    match result {
+        Ok(report) => report.availability.to_string(),
+        Err(e) => e.to_string(),
+    }
+}
+
+fn result_is_available(result: &CheckResult) -> bool {
+    matches!(result, Ok(report) if report.availability == Availability::Available)
+}
+
+/// Append parenthesized notes to a status: `available (similar to `serde`)`.
+fn with_notes(status: String, notes: &[String]) -> String {
+    if notes.is_empty() {
+        status
+    } else {
+        format!("{status} ({})", notes.join(", "))
+    }
+}
+
+/// Print crate metadata as aligned `label  value` lines under a result.
+fn print_details(details: &CrateDetails) {
+    // RFC 3339 timestamps: the date is enough at a glance
+    let date = |ts: &str| ts.get(..10).unwrap_or(ts).to_string();
+    let or_dash = |v: &Option<String>| v.as_deref().map_or("-".to_string(), sanitize);
+    let downloads = match details.recent_downloads {
+        Some(recent) => format!("{} ({recent} recent)", details.downloads),
+        None => details.downloads.to_string(),
+    };
+    let rows = [
+        ("description", or_dash(&details.description)),
+        ("created", date(&details.created_at)),
+        ("updated", date(&details.updated_at)),
+        ("max version", sanitize(&details.max_version)),
+        ("downloads", downloads),
+        ("versions", details.versions.to_string()),
+        ("repository", or_dash(&details.repository)),
+        ("homepage", or_dash(&details.homepage)),
+    ];
+    for (label, value) in rows {
+        println!("  {label:<12} {value}");
+    }
+}
+
+/// Print the most downloaded crates sharing an available name's stem.
+fn print_related(namespace: &Namespace) {
+    for (i, related) in namespace.top.iter().enumerate() {
+        let label = if i == 0 { "related" } else { "" };
+        println!(
+            "  {label:<12} {} ({} downloads)",
+            sanitize(&related.name),
+            related.downloads
+        );
+    }
+}
+
+/// Render a check result as the `status` and `error` fields of the JSON output.
+fn json_status(result: &CheckResult) -> (String, Option<String>) {
+    match result {
+        Ok(report) => (report.availability.to_string(), None),
+        Err(e @ (CheckError::InvalidName(_) | CheckError::Lookalike(_))) => {
+            ("invalid".to_string(), Some(e.to_string()))
+        }
+        Err(e) => ("error".to_string(), Some(e.to_string())),
+    }
+}
+
+/// The package name and binary names of the manifest at `path`.
+fn read_manifest_binaries(path: &std::path::Path) -> Result<(String, Vec<String>), String> {
+    let manifest: toml::Table = std::fs::read_to_string(path)
+        .map_err(|e| format!("reading {}: {e}", path.display()))?
+        .parse()
+        .map_err(|e| format!("parsing {}: {e}", path.display()))?;
+    manifest_binaries(&manifest).ok_or_else(|| format!("{}: no [package] name", path.display()))
+}
+
+/// Append one name per non-empty line of stdin, if stdin is not a terminal.
+fn read_stdin_names(names: &mut Vec<String>) -> io::Result<()> {
+    if io::stdin().is_terminal() {
+        return Ok(());
+    }
+    for line in io::stdin().lock().lines() {
+        let line = line?;
+        let trimmed = line.trim();
+        if !trimmed.is_empty() {
+            names.push(trimmed.to_string());
+        }
+    }
+    Ok(())
+}
+
+/// `cargo avail owners`: list each crate's owners and report drift from the
+/// expected-owners file, if one is given.
+fn run_owners(args: OwnersArgs) -> ExitCode {
+    let expected = match &args.expected {
+        Some(path) => match std::fs::read_to_string(path)
+            .map_err(|e| e.to_string())
+            .and_then(|s| s.parse::<ExpectedOwners>().map_err(|e| e.to_string()))
+        {
+            Ok(expected) => Some(expected),
+            Err(e) => {
+                eprintln!("error: {}: {e}", path.display());
+                return ExitCode::from(2);
+            }
+        },
+        None => None,
+    };
+
+    let mut names = args.names;
+    if let Err(e) = read_stdin_names(&mut names) {
+        eprintln!("error: reading stdin: {e}");
+        return ExitCode::from(2);
+    }
+    if names.is_empty() {
+        eprintln!("error: no crate names provided");
+        eprintln!("usage: cargo avail owners [OPTIONS] [NAMES...]");
+        return ExitCode::from(2);
+    }
+    let mut seen = std::collections::HashSet::new();
+    names.retain(|n| seen.insert(canon_crate_name(n)));
+
+    let client = Client::new();
+    let results = run_all(&names, |name| crate_owners(&client, name));
+
+    let mut any_drift = false;
+    let mut error_count: usize = 0;
+
+    for (name, result) in &results {
+        let drift = match (&expected, result) {
+            (Some(expected), Ok(Some(owners))) => expected.audit(owners),
+            _ => Vec::new(),
+        };
+        let (status, error) = match result {
+            Ok(Some(_)) if drift.is_empty() => ("ok".to_string(), None),
+            Ok(Some(_)) => ("drift".to_string(), None),
+            Ok(None) => ("not found".to_string(), None),
+            Err(e @ (CheckError::InvalidName(_) | CheckError::Lookalike(_))) => {
+                ("invalid".to_string(), Some(e.to_string()))
+            }
+            Err(e) => ("error".to_string(), Some(e.to_string())),
+        };
+        match result {
+            Ok(Some(_)) if drift.is_empty() => {}
+            Err(CheckError::IndexLookup(_) | CheckError::Internal(_)) => error_count += 1,
+            _ => any_drift = true,
+        }
+
+        if args.quiet {
+            continue;
+        }
+
+        let owners = result.as_ref().ok().and_then(Option::as_deref);
+        if args.json {
+            let json_result = JsonOwners {
+                name: name.clone(),
+                status,
+                error,
+                owners,
+                drift: &drift,
+            };
+            println!(
+                "{}",
+                serde_json::to_string(&json_result).expect("JSON serialization should not fail")
+            );
+            continue;
+        }
+
+        // Drift explains itself; otherwise list who owns the crate
+        let notes: Vec<String> = if drift.is_empty() {
+            owners
+                .unwrap_or_default()
+                .iter()
+                .map(|o| o.login.clone())
+                .collect()
+        } else {
+            drift.iter().map(Drift::to_string).collect()
+        };
+        let status = match result {
+            Err(e) => e.to_string(),
+            Ok(_) => with_notes(status, &notes),
+        };
+        println!("{}\t{}", sanitize(name), sanitize(&status));
+    }
+
+    if error_count > 0 && !args.quiet {
+        eprintln!(
+            "warning: {error_count} crate{} could not be checked (network error)",
+            if error_count == 1 { "" } else { "s" }
+        );
+    }
+
+    if error_count > 0 {
+        ExitCode::from(3)
+    } else if any_drift {
+        ExitCode::from(1)
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+/// `cargo avail claim-request`: print a draft transfer request for review.
+fn run_claim_request(args: &ClaimArgs) -> ExitCode {
+    let options = ClassifyOptions {
+        dormant_years: args.dormant_years,
+        ..ClassifyOptions::default()
+    };
+    let client = Client::new();
+    let mut request = match ClaimRequest::gather(&client, &args.name, &options) {
+        Ok(request) => request,
+        Err(e) => {
+            eprintln!("error: {}: {e}", sanitize(&args.name));
+            return match e {
+                ClaimError::Check(CheckError::IndexLookup(_) | CheckError::Internal(_)) => {
+                    ExitCode::from(3)
+                }
+                _ => ExitCode::from(1),
+            };
+        }
+    };
+    if args.check_repository {
+        request.check_repository(&client);
+    }
+    if request.classification.label == Label::Active {
+        eprintln!(
+            "warning: `{}` looks actively maintained; a transfer is unlikely to be granted",
+            request.name
+        );
+    }
+    print!("{}", request.render(args.format));
+    eprintln!(
+        "note: this is only a draft; fill in the placeholders, review it and send it to \
+         {HELP_ADDRESS} yourself"
+    );
+    ExitCode::SUCCESS
+}
+
+/// `cargo avail family`: check a base name and its member crates as one set.
+fn run_family(args: &FamilyArgs) -> ExitCode {
+    let names = if args.members.is_empty() {
+        family(&args.base, DEFAULT_FAMILY_MEMBERS)
+    } else {
+        family(&args.base, &args.members)
+    };
+    let client = match args.owners.apply(Client::new()) {
+        Ok(client) => client,
+        Err(e) => {
+            eprintln!("error: {e}");
+            return ExitCode::from(2);
+        }
+    };
+    let results = check_all(&client, &names);
+
+    let is_network_error = |result: &CheckResult| {
+        matches!(
+            result,
+            Err(CheckError::IndexLookup(_) | CheckError::Internal(_))
+        )
+    };
+    // Names you already own don't block, as in the main check
+    let blocked: Vec<&String> = results
+        .iter()
+        .filter(|(_, result)| {
+            !result_is_available(result)
+                && !is_network_error(result)
+                && !matches!(result, Ok(r) if r.availability == Availability::Owned)
+        })
+        .map(|(name, _)| name)
+        .collect();
+    let error_count = results.iter().filter(|(_, r)| is_network_error(r)).count();
+
+    if args.json {
+        let json_family = JsonFamily {
+            family: args.base.clone(),
+            claimable: blocked.is_empty() && error_count == 0,
+            members: results
+                .iter()
+                .map(|(name, result)| {
+                    let (status, error) = json_status(result);
+                    JsonAlternative {
+                        name: name.clone(),
+                        status,
+                        error,
+                    }
+                })
+                .collect(),
+            blocked: blocked.iter().map(|name| (*name).clone()).collect(),
+        };
+        println!(
+            "{}",
+            serde_json::to_string(&json_family).expect("JSON serialization should not fail")
+        );
+    } else if !args.quiet {
+        for (name, result) in &results {
+            println!("{}\t{}", sanitize(name), sanitize(&status_string(result)));
+        }
+        let base = sanitize(&args.base);
+        if !blocked.is_empty() {
+            let blocked: Vec<String> = blocked.iter().map(|name| sanitize(name)).collect();
+            eprintln!("family {base}: blocked by {}", blocked.join(", "));
+        } else if error_count == 0 {
+            eprintln!("family {base}: all {} names can be claimed", names.len());
+        }
+    }
+
+    if error_count > 0 && !args.quiet {
+        eprintln!(
+            "warning: {error_count} name{} could not be checked (network error)",
+            if error_count == 1 { "" } else { "s" }
+        );
+    }
+
+    if error_count > 0 {
+        ExitCode::from(3)
+    } else if !blocked.is_empty() {
+        ExitCode::from(1)
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+/// How a search through generated candidates went.
+#[derive(Default)]
+struct Search {
+    found: usize,
+    checked: usize,
+    error_count: usize,
+    /// Whether unchecked candidates were left when the search stopped.
+    more_left: bool,
+}
+
+/// Check generated candidates, in order, in small rounds with a pause in
+/// between, until `count` are available, `max_checks` have been checked or
+/// the candidates run out. `on_found` is called for each available one.
+fn search<T>(
+    candidates: impl IntoIterator<Item = T>,
+    name_of: impl Fn(&T) -> String,
+    count: usize,
+    max_checks: usize,
+    mut on_found: impl FnMut(&T, &CheckResult),
+) -> Search {
+    let client = Client::new();
+    let mut candidates = candidates.into_iter().peekable();
+    let mut search = Search::default();
+    while search.found < count && search.checked < max_checks && candidates.peek().is_some() {
+        if search.checked > 0 {
+            std::thread::sleep(CANDIDATE_PAUSE);
+        }
+        let round = CANDIDATE_BATCH.min(max_checks - search.checked);
+        let batch: Vec<T> = candidates.by_ref().take(round).collect();
+        let names: Vec<String> = batch.iter().map(&name_of).collect();
+        search.checked += batch.len();
+        for ((_, result), candidate) in check_all(&client, &names).iter().zip(&batch) {
+            if matches!(
+                result,
+                Err(CheckError::IndexLookup(_) | CheckError::Internal(_))
+            ) {
+                search.error_count += 1;
+            } else if result_is_available(result) && search.found < count {
+                search.found += 1;
+                on_found(candidate, result);
+            }
+        }
+    }
+    search.more_left = candidates.peek().is_some();
+    search
+}
+
+/// Report a search that fell short on stderr and pick the exit code: 0 if
+/// `count` names were found, 3 if not and some could not be checked, else 1.
+fn finish_search(search: &Search, count: usize, quiet: bool) -> ExitCode {
+    if !quiet {
+        if search.error_count > 0 {
+            eprintln!(
+                "warning: {} candidate{} could not be checked (network error)",
+                search.error_count,
+                if search.error_count == 1 { "" } else { "s" }
+            );
+        }
+        if search.found < count {
+            eprintln!(
+                "note: found {} of {count} available names in {} candidates{}",
+                search.found,
+                search.checked,
+                if search.more_left {
+                    " (raise --max-checks to check more)"
+                } else {
+                    ""
+                }
+            );
+        }
+    }
+    if search.found == count {
+        ExitCode::SUCCESS
+    } else if search.error_count > 0 {
+        ExitCode::from(3)
+    } else {
+        ExitCode::from(1)
+    }
+}
+
+/// `cargo avail suggest`: check generated candidates, best first, until enough
+/// available names are found.
+fn run_suggest(args: &SuggestArgs) -> ExitCode {
+    if args.count == 0 {
+        eprintln!("error: --count must be at least 1");
+        return ExitCode::from(2);
+    }
+    let mut keywords = args.keywords.clone();
+    if let Some(dir) = &args.from_project {
+        let terms = match Project::load(dir) {
+            Ok(project) => project.terms(args.terms),
+            Err(e) => {
+                eprintln!("error: {e}");
+                return ExitCode::from(2);
+            }
+        };
+        if !args.quiet {
+            if terms.is_empty() {
+                eprintln!("note: no keywords found in {}", dir.display());
+            } else {
+                eprintln!(
+                    "note: keywords from {}: {}",
+                    dir.display(),
+                    terms.join(", ")
+                );
+            }
+        }
+        keywords.extend(terms);
+    }
+    let candidates = candidates(&keywords, &args.options());
+    if candidates.is_empty() {
+        if !args.quiet {
+            eprintln!("error: no valid candidates can be built from these keywords");
+        }
+        return ExitCode::from(1);
+    }
+    let search = search(
+        &candidates,
+        |candidate| candidate.name.clone(),
+        args.count,
+        args.max_checks,
+        |candidate, result| print_suggestion(args, candidate, result),
+    );
+    finish_search(&search, args.count, args.quiet)
+}
 
-#[derive(Serialize)]
-struct JsonResult {
-    name: String,
-    status: String,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    error: Option<String>,
+/// `cargo avail random`: generate pronounceable names until enough are
+/// available.
+fn run_random(args: &RandomArgs) -> ExitCode {
+    if args.count == 0 {
+        eprintln!("error: --count must be at least 1");
+        return ExitCode::from(2);
+    }
+    let seed = args.seed.unwrap_or_else(|| {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default();
+        // Only the low bits of the clock vary between runs anyway
+        (now.as_nanos() as u64) ^ u64::from(std::process::id())
+    });
+    if args.seed.is_none() && !args.quiet {
+        eprintln!("note: seed {seed} (pass --seed {seed} to get these names again)");
+    }
+    let names = NameModel::bundled().names(args.len, seed);
+    let search = search(
+        names,
+        Clone::clone,
+        args.count,
+        args.max_checks,
+        |name, result| {
+            if args.quiet {
+                return;
+            }
+            if args.json {
+                let (status, error) = json_status(result);
+                let json_result = JsonAlternative {
+                    name: name.clone(),
+                    status,
+                    error,
+                };
+                println!(
+                    "{}",
+                    serde_json::to_string(&json_result)
+                        .expect("JSON serialization should not fail")
+                );
+            } else {
+                println!("{name}\t{}", status_string(result));
+            }
+        },
+    );
+    finish_search(&search, args.count, args.quiet)
 }
 
-#[derive(Parser)]
-#[command(
-    name = "cargo-avail",
-    bin_name = "cargo avail",
-    version,
-    about = "Check whether crate names are truly available on crates.io",
-    after_help = "Checks name validity (character rules, length), reserved names \
-                  (std, core, alloc, nul, com0, etc.), and the crates.io API \
-                  with canonical matching (hyphens and underscores are equivalent).\n\n\
-                  Cannot detect recently deleted crates (requires DB access). \
-                  A name passing all checks could still fail at publish time."
-)]
-struct Cli {
-    /// Crate names to check (also reads from stdin)
-    names: Vec<String>,
+/// Read the name index at `path`, or at the default path.
+fn read_index(path: Option<&Path>) -> Result<NameIndex, String> {
+    let path = match path {
+        Some(path) => path.to_path_buf(),
+        None => index::default_path().ok_or("no cargo home to find the name index in")?,
+    };
+    NameIndex::read(&path).map_err(|e| match e {
+        IndexError::Io(_, ref io) if io.kind() == io::ErrorKind::NotFound => format!(
+            "no name index at {} (run `cargo avail index build` first)",
+            path.display()
+        ),
+        e => e.to_string(),
+    })
+}
 
-    /// Suppress output, exit code only
-    #[arg(short, long, conflicts_with = "json")]
-    quiet: bool,
+fn run_index_build(args: &IndexBuildArgs) -> ExitCode {
+    let Some(output) = args.output.clone().or_else(index::default_path) else {
+        eprintln!("error: no cargo home to write the name index to (pass --output)");
+        return ExitCode::from(2);
+    };
+    let index = match index::read_source(&args.source) {
+        Ok(names) => NameIndex::build(names),
+        Err(e) => {
+            eprintln!("error: {e}");
+            return ExitCode::from(2);
+        }
+    };
+    if let Err(e) = index.write(&output) {
+        eprintln!("error: {e}");
+        return ExitCode::from(2);
+    }
+    if !args.quiet {
+        eprintln!(
+            "note: indexed {} names into {} ({} bytes)",
+            index.len(),
+            output.display(),
+            index.to_bytes().len()
+        );
+    }
+    ExitCode::SUCCESS
+}
 
-    /// Only print available names
-    #[arg(short, long, conflicts_with = "json")]
-    available_only: bool,
+fn run_index_query(args: &IndexQueryArgs) -> ExitCode {
+    if let Some(query) = args.queries.iter().find(|q| q.ends_with('*')) {
+        if args.distance.is_some() {
+            eprintln!("error: --distance cannot be used with the prefix query `{query}`");
+            return ExitCode::from(2);
+        }
+    }
+    let index = match read_index(args.index.as_deref()) {
+        Ok(index) => index,
+        Err(e) => {
+            eprintln!("error: {e}");
+            return ExitCode::from(2);
+        }
+    };
+    let flatten = |m: IndexMatch| (m.name, m.distance);
+    let mut found = false;
+    for query in &args.queries {
+        let matches: Vec<(String, usize)> = match (query.strip_suffix('*'), args.distance) {
+            (Some(prefix), _) => index.prefix(prefix).into_iter().map(flatten).collect(),
+            (None, Some(distance)) => index
+                .fuzzy(query, distance)
+                .into_iter()
+                .map(flatten)
+                .collect(),
+            (None, None) => index.get(query).map(|name| (name, 0)).into_iter().collect(),
+        };
+        found |= !matches.is_empty();
+        if args.quiet {
+            continue;
+        }
+        for (name, distance) in matches {
+            if args.json {
+                let json_match = JsonIndexMatch {
+                    query,
+                    name,
+                    distance,
+                };
+                println!(
+                    "{}",
+                    serde_json::to_string(&json_match).expect("JSON serialization should not fail")
+                );
+            } else if args.distance.is_some() {
+                println!("{name}\t{distance}");
+            } else {
+                println!("{name}");
+            }
+        }
+    }
+    if found {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::from(1)
+    }
+}
 
-    /// Output results as NDJSON (one JSON object per line)
-    #[arg(long)]
+/// Print one available suggestion as a text line or a JSON object.
+fn print_suggestion(args: &SuggestArgs, candidate: &Candidate, result: &CheckResult) {
+    if args.quiet {
+        return;
+    }
+    let status = status_string(result);
+    if args.json {
+        let json_suggestion = JsonSuggestion {
+            name: &candidate.name,
+            status,
+            score: candidate.score,
+            origin: candidate.origin,
+        };
+        println!(
+            "{}",
+            serde_json::to_string(&json_suggestion).expect("JSON serialization should not fail")
+        );
+    } else {
+        let notes = [format!("score {}", candidate.score.total)];
+        println!("{}\t{}", candidate.name, with_notes(status, &notes));
+    }
+}
+
+/// `cargo avail combine`: check the product of several wordlists, printing
+/// each round of results as soon as it is in.
+fn run_combine(args: &CombineArgs) -> ExitCode {
+    let mut lists = Vec::with_capacity(args.files.len());
+    for path in &args.files {
+        match std::fs::read_to_string(path) {
+            Ok(text) if parse_wordlist(&text).is_empty() => {
+                eprintln!("error: {}: no words", path.display());
+                return ExitCode::from(2);
+            }
+            Ok(text) => lists.push(parse_wordlist(&text)),
+            Err(e) => {
+                eprintln!("error: {}: {e}", path.display());
+                return ExitCode::from(2);
+            }
+        }
+    }
+    let mut combinations = Combinations::new(lists, &args.sep);
+    if let Some(max_len) = args.max_len {
+        combinations = combinations.with_max_len(max_len);
+    }
+    // A sample is bounded by its size; the full product by --max-checks
+    let (names, max_checks): (Box<dyn Iterator<Item = String>>, usize) = match args.sample {
+        Some(count) => (
+            Box::new(combinations.sample(count, args.seed).into_iter()),
+            count,
+        ),
+        None => (Box::new(combinations.iter()), args.max_checks),
+    };
+
+    let client = Client::new();
+    // Names are printed the way the main check prints them with no options
+    let mut printer = Printer {
+        json: args.json,
+        available_only: args.available_only,
+        ..Printer::default()
+    };
+    let mut names = names.peekable();
+    let (mut checked, mut available, mut error_count) = (0usize, 0usize, 0usize);
+    while checked < max_checks && names.peek().is_some() {
+        if checked > 0 {
+            std::thread::sleep(CANDIDATE_PAUSE);
+        }
+        let round = CANDIDATE_BATCH.min(max_checks - checked);
+        let chunk: Vec<String> = names.by_ref().take(round).collect();
+        let results = check_all(&client, &chunk);
+        printer.scores.extend(
+            results
+                .iter()
+                .filter(|(_, result)| result_is_available(result))
+                .map(|(name, _)| (name.clone(), score(name))),
+        );
+        for (name, result) in &results {
+            checked += 1;
+            if result_is_available(result) {
+                available += 1;
+            }
+            if matches!(
+                result,
+                Err(CheckError::IndexLookup(_) | CheckError::Internal(_))
+            ) {
+                error_count += 1;
+            }
+            if !args.quiet {
+                printer.print(name, result, &printer.findings(name, result));
+            }
+        }
+        // Show each round as it arrives, even when piped
+        let _ = io::stdout().flush();
+    }
+
+    if !args.quiet {
+        if error_count > 0 {
+            eprintln!(
+                "warning: {error_count} name{} could not be checked (network error)",
+                if error_count == 1 { "" } else { "s" }
+            );
+        }
+        eprintln!(
+            "note: {available} of {checked} names available ({} combinations before pruning){}",
+            combinations.total(),
+            if names.peek().is_some() {
+                "; stopped at --max-checks, raise it to check more"
+            } else {
+                ""
+            }
+        );
+    }
+
+    if available > 0 {
+        ExitCode::SUCCESS
+    } else if error_count > 0 {
+        ExitCode::from(3)
+    } else {
+        ExitCode::from(1)
+    }
+}
+
+/// Everything printing a checked name needs beyond its own result: the output
+/// options and the lookups made for all names together. The main check fills
+/// it from its flags; `combine` prints with the defaults, so both produce the
+/// same lines and JSON objects.
+#[derive(Default)]
+struct Printer {
     json: bool,
+    available_only: bool,
+    details: bool,
+    /// Whether results are ranked by score, which then shows in text output.
+    by_score: bool,
+    no_similar: bool,
+    no_subcommand_check: bool,
+    /// Set with `--classify-taken`.
+    classify: Option<ClassifyOptions>,
+    lint_config: Option<LintConfig>,
+    entrenched: Option<u64>,
+    distros: Vec<Distro>,
+    packages: Option<PackageList>,
+    aliases: HashMap<String, String>,
+    bin_check: bool,
+    /// Binaries `cargo install` put on `PATH`, which are no collision for
+    /// the package that installed them.
+    installs: Installs,
+    /// The `--manifest-path` package name and its binaries.
+    manifest: Option<(String, Vec<String>)>,
+    search_path: Option<OsString>,
+    fixed_from: HashMap<String, String>,
+    expanded_from: HashMap<String, String>,
+    fixes: HashMap<String, (String, CheckResult)>,
+    variant_names: HashMap<String, Vec<String>>,
+    variant_results: HashMap<String, CheckResult>,
+    rdeps: HashMap<String, Result<Option<u64>, CheckError>>,
+    namespaces: HashMap<String, Result<Namespace, CheckError>>,
+    scores: HashMap<String, Score>,
 }
 
-/// Sanitize a string for tab-separated output: replace control chars with escape sequences.
-fn sanitize(s: &str) -> String {
-    let mut out = String::with_capacity(s.len());
-    for ch in s.chars() {
-        match ch {
-            '\n' => out.push_str("\\n"),
-            '\r' => out.push_str("\\r"),
-            '\t' => out.push_str("\\t"),
-            '\0' => out.push_str("\\0"),
-            c if c.is_control() => {
-                let _ = write!(out, "\\x{:02x}", c as u32);
+impl Printer {
+    /// Lint findings for `name`; lints only make sense for names that passed
+    /// validation.
+    fn findings(&self, name: &str, result: &CheckResult) -> Vec<Finding> {
+        match (&self.lint_config, result) {
+            (Some(config), Ok(_)) => lint_name(name, config),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Print one checked name as a text line (with its details and variants)
+    /// or a JSON object.
+    fn print(&self, name: &str, result: &CheckResult, findings: &[Finding]) {
+        // A denied lint makes an otherwise available name count as unavailable
+        let is_available = result_is_available(result) && !any_denied(findings);
+        let is_network_error = matches!(
+            result,
+            Err(CheckError::IndexLookup(_) | CheckError::Internal(_))
+        );
+        let rdep_count = match self.rdeps.get(name) {
+            Some(Ok(count)) => *count,
+            _ => None,
+        };
+        let entrenched = self
+            .entrenched
+            .zip(rdep_count)
+            .map(|(threshold, count)| count >= threshold);
+        let namespace = self.namespaces.get(name).and_then(|r| r.as_ref().ok());
+        let fix = self.fixes.get(name);
+        let group: Vec<(&String, &CheckResult)> = self
+            .variant_names
+            .get(name)
+            .into_iter()
+            .flatten()
+            .filter_map(|variant| self.variant_results.get(variant).map(|r| (variant, r)))
+            .collect();
+        let classification = match (result, &self.classify) {
+            (
+                Ok(Report {
+                    details: Some(details),
+                    ..
+                }),
+                Some(options),
+            ) => Some(classify(details, options)),
+            _ => None,
+        };
+        // Similarity stage: warn when an available name resembles a popular crate
+        let similar = if is_available && !self.no_similar {
+            find_similar(name)
+        } else {
+            None
+        };
+        // A `cargo-*` crate is also a command, which must not clash with another.
+        // This needs no lookup, so it is reported even if the check failed.
+        let is_invalid = matches!(
+            result,
+            Err(CheckError::InvalidName(_) | CheckError::Lookalike(_))
+        );
+        let conflicts = if !is_invalid && !self.no_subcommand_check {
+            subcommand_conflicts(name, &self.aliases)
+        } else {
+            Vec::new()
+        };
+        // Binaries named after the crate, or the manifest's [[bin]] targets
+        let binaries: Vec<BinaryWarning> = if self.bin_check && !is_invalid {
+            let own = [name.to_string()];
+            let binaries = match &self.manifest {
+                Some((package, binaries))
+                    if canon_crate_name(package) == canon_crate_name(name) =>
+                {
+                    binaries.as_slice()
+                }
+                _ => &own,
+            };
+            binaries
+                .iter()
+                .flat_map(|binary| binary_clashes(binary, self.search_path.as_deref()))
+                .filter(|warning| !self.installs.is_own(warning, name))
+                .collect()
+        } else {
+            Vec::new()
+        };
+        // Distribution packages, named after the crate whatever its status
+        let packaging: Vec<DistroIssue> = self
+            .distros
+            .iter()
+            .flat_map(|&distro| distro_issues(name, distro, self.packages.as_ref()))
+            .collect();
+
+        if self.json {
+            let (status, error) = json_status(result);
+            let suggestion = match result {
+                Err(CheckError::Lookalike(e)) => e.suggestion.clone(),
+                _ => None,
+            };
+            let collision = result.as_ref().ok().and_then(|r| r.collision.as_ref());
+            let json_result = JsonResult {
+                name: name.to_string(),
+                status,
+                error,
+                collides_with: collision.map(|c| c.name.clone()),
+                collision: collision.map(|c| c.kind.to_string()),
+                yanked_only: result.as_ref().is_ok_and(Report::is_yanked_only),
+                rdeps: rdep_count,
+                entrenched,
+                suggestion,
+                fixed_from: self.fixed_from.get(name).cloned(),
+                pattern: self.expanded_from.get(name).cloned(),
+                fix: fix.map(|(fixed, fix_result)| {
+                    let (status, error) = json_status(fix_result);
+                    JsonAlternative {
+                        name: fixed.clone(),
+                        status,
+                        error,
+                    }
+                }),
+                variants: group
+                    .iter()
+                    .map(|(variant, variant_result)| {
+                        let (status, error) = json_status(variant_result);
+                        JsonAlternative {
+                            name: (*variant).clone(),
+                            status,
+                            error,
+                        }
+                    })
+                    .collect(),
+                similar_to: similar.as_ref().map(|s| JsonSimilar {
+                    name: s.name.clone(),
+                    resemblance: s.resemblance.to_string(),
+                }),
+                subcommand_conflicts: conflicts
+                    .iter()
+                    .map(|c| JsonConflict {
+                        kind: c.kind.label().to_string(),
+                        command: c.command.clone(),
+                        message: c.to_string(),
+                    })
+                    .collect(),
+                binary_clashes: binaries
+                    .iter()
+                    .map(|w| JsonBinaryClash {
+                        binary: w.binary.clone(),
+                        kind: w.clash.label().to_string(),
+                        path: match &w.clash {
+                            BinaryClash::OnPath(path) => Some(path.clone()),
+                            _ => None,
+                        },
+                        message: w.to_string(),
+                    })
+                    .collect(),
+                distro_packages: self
+                    .distros
+                    .iter()
+                    .map(|distro| JsonDistroPackage {
+                        distro: distro.to_string(),
+                        package: distro.source_package(name),
+                    })
+                    .collect(),
+                distro_issues: packaging
+                    .iter()
+                    .map(|issue| JsonDistroIssue {
+                        distro: issue.distro.to_string(),
+                        kind: issue.kind.label().to_string(),
+                        package: issue.package.clone(),
+                        message: issue.to_string(),
+                    })
+                    .collect(),
+                score: self.scores.get(name).copied(),
+                related: namespace,
+                details: result
+                    .as_ref()
+                    .ok()
+                    .filter(|_| self.details)
+                    .and_then(|r| r.details.as_ref()),
+                classification,
+                lints: findings
+                    .iter()
+                    .map(|f| JsonLint {
+                        lint: f.lint.to_string(),
+                        level: f.level.to_string(),
+                        message: f.message.clone(),
+                    })
+                    .collect(),
+            };
+            println!(
+                "{}",
+                serde_json::to_string(&json_result).expect("JSON serialization should not fail")
+            );
+            return;
+        }
+
+        // --available-only hides taken/reserved/invalid but always shows errors,
+        // and shows names whose suggested fix or a variant is available
+        let fix_available = fix.is_some_and(|(_, r)| result_is_available(r));
+        let variant_available = group.iter().any(|(_, r)| result_is_available(r));
+        if self.available_only
+            && !is_available
+            && !is_network_error
+            && !fix_available
+            && !variant_available
+        {
+            return;
+        }
+
+        let sanitized_name = sanitize(name);
+        let mut notes = result.as_ref().map(Report::notes).unwrap_or_default();
+        if entrenched == Some(true) {
+            notes.push("entrenched".to_string());
+        }
+        match (self.rdeps.get(name), rdep_count) {
+            (_, Some(count)) => notes.push(format!("{count} rdeps")),
+            (Some(Err(_)), _) => notes.push("rdeps unknown".to_string()),
+            _ => {}
+        }
+        if let Some(classification) = &classification {
+            let mut note = classification.label.to_string();
+            if !classification.reasons.is_empty() {
+                let _ = write!(note, ": {}", classification.reasons.join("; "));
             }
-            c => out.push(c),
+            notes.push(note);
+        }
+        if let Some(similar) = &similar {
+            notes.push(similar.to_string());
+        }
+        notes.extend(conflicts.iter().map(ToString::to_string));
+        notes.extend(binaries.iter().map(ToString::to_string));
+        // Every distribution names the source package the same way
+        if let Some(distro) = self.distros.first().filter(|_| !is_invalid) {
+            notes.push(format!("packaged as {}", distro.source_package(name)));
+        }
+        notes.extend(packaging.iter().map(ToString::to_string));
+        if let Some(score) = self.scores.get(name).filter(|_| self.by_score) {
+            notes.push(format!("score {}", score.total));
+        }
+        match (self.namespaces.get(name), namespace) {
+            (_, Some(namespace)) if namespace.is_crowded() => notes.push(format!(
+                "crowded: {} related crate{}",
+                namespace.total,
+                if namespace.total == 1 { "" } else { "s" }
+            )),
+            (Some(Err(_)), _) => notes.push("crowding unknown".to_string()),
+            _ => {}
+        }
+        notes.extend(findings.iter().map(|f| format!("{}: {}", f.level, f.lint)));
+        let sanitized_status = sanitize(&with_notes(status_string(result), &notes));
+        match fix {
+            Some((fixed, fix_result)) => println!(
+                "{sanitized_name}\t{sanitized_status}\tfix: {fixed} {}",
+                sanitize(&status_string(fix_result))
+            ),
+            None => println!("{sanitized_name}\t{sanitized_status}"),
+        }
+        if self.details {
+            if let Ok(Report {
+                details: Some(details),
+                ..
+            }) = result
+            {
+                print_details(details);
+            }
+            if let Some(namespace) = namespace {
+                print_related(namespace);
+            }
+        }
+        // Variants are grouped under the name they were derived from
+        for (variant, variant_result) in &group {
+            if self.available_only && !result_is_available(variant_result) {
+                continue;
+            }
+            println!(
+                "  {}\t{}",
+                sanitize(variant),
+                sanitize(&status_string(variant_result))
+            );
         }
     }
-    out
 }
 
 fn main() -> ExitCode {
@@ -88,24 +1858,85 @@ fn main() -> ExitCode {
         args
     };
 
-    let cli = Cli::parse_from(args);
+    let mut cli = Cli::parse_from(args);
+    let lint_config = cli.lint_config();
+    let classify_options = ClassifyOptions {
+        dormant_years: cli.dormant_years,
+        ..ClassifyOptions::default()
+    };
 
-    let mut names: Vec<String> = cli.names;
+    match cli.command.take() {
+        Some(Command::Owners(args)) => return run_owners(args),
+        Some(Command::ClaimRequest(args)) => return run_claim_request(&args),
+        Some(Command::Family(args)) => return run_family(&args),
+        Some(Command::Suggest(args)) => return run_suggest(&args),
+        Some(Command::Combine(args)) => return run_combine(&args),
+        Some(Command::Random(args)) => return run_random(&args),
+        Some(Command::Index(IndexCommand::Build(args))) => return run_index_build(&args),
+        Some(Command::Index(IndexCommand::Query(args))) => return run_index_query(&args),
+        None => {}
+    }
 
-    // Read from stdin if not a terminal
-    if !io::stdin().is_terminal() {
-        for line in io::stdin().lock().lines() {
-            match line {
-                Ok(l) => {
-                    let trimmed = l.trim().to_string();
-                    if !trimmed.is_empty() {
-                        names.push(trimmed);
-                    }
-                }
+    let mut names: Vec<String> = std::mem::take(&mut cli.names);
+    if let Err(e) = read_stdin_names(&mut names) {
+        eprintln!("error: reading stdin: {e}");
+        return ExitCode::from(2);
+    }
+
+    // Expand brace patterns, remembering which pattern produced each name.
+    // A name given more than once keeps its first source, as deduplication
+    // below keeps its first occurrence.
+    let mut expanded_from = std::collections::HashMap::new();
+    if cli.expand {
+        let mut expanded = Vec::with_capacity(names.len());
+        let mut sources = std::collections::HashSet::new();
+        for pattern in names {
+            let list = match expand(&pattern, cli.max_expansion) {
+                Ok(list) => list,
                 Err(e) => {
-                    eprintln!("error: reading stdin: {e}");
+                    eprintln!("error: {}", sanitize(&e.to_string()));
                     return ExitCode::from(2);
                 }
+            };
+            let is_pattern = list != [pattern.as_str()];
+            for name in list {
+                if sources.insert(canon_crate_name(&name)) && is_pattern {
+                    expanded_from.insert(name.clone(), pattern.clone());
+                }
+                expanded.push(name);
+            }
+        }
+        names = expanded;
+    }
+
+    // The binaries of the manifest's package, checked with --bin-check; with
+    // no names given, the package name is checked
+    let manifest = match &cli.manifest_path {
+        Some(path) => match read_manifest_binaries(path) {
+            Ok(manifest) => Some(manifest),
+            Err(e) => {
+                eprintln!("error: {e}");
+                return ExitCode::from(2);
+            }
+        },
+        None => None,
+    };
+    if let Some((package, _)) = &manifest {
+        if names.is_empty() {
+            names.push(package.clone());
+        }
+    }
+    let bin_check = cli.bin_check || manifest.is_some();
+    let search_path = std::env::var_os("PATH");
+
+    // Existing distribution packages, merged from every list given
+    let mut packages: Option<PackageList> = None;
+    for path in &cli.distro_packages {
+        match PackageList::read(path) {
+            Ok(list) => packages.get_or_insert_default().extend(list),
+            Err(e) => {
+                eprintln!("error: reading {}: {e}", path.display());
+                return ExitCode::from(2);
             }
         }
     }
@@ -116,45 +1947,178 @@ fn main() -> ExitCode {
         return ExitCode::from(2);
     }
 
+    // Replace names containing homoglyphs or invisible characters with their
+    // ASCII transliteration, remembering the original for the output.
+    let mut fixed_from = std::collections::HashMap::new();
+    if cli.fix {
+        for name in &mut names {
+            if let Err(e) = check_lookalikes(name) {
+                if let Some(suggestion) = e.suggestion {
+                    if !cli.quiet && !cli.json {
+                        eprintln!(
+                            "note: checking `{suggestion}` instead of `{}`",
+                            sanitize(name)
+                        );
+                    }
+                    fixed_from.insert(suggestion.clone(), std::mem::replace(name, suggestion));
+                }
+            }
+        }
+    }
+
     // Deduplicate by canonical name while preserving order and original input
     let mut seen = std::collections::HashSet::new();
     names.retain(|n| seen.insert(canon_crate_name(n)));
 
-    let client = Client::new();
+    let client = match cli.client() {
+        Ok(client) => client,
+        Err(e) => {
+            eprintln!("error: {e}");
+            return ExitCode::from(2);
+        }
+    };
 
-    // Check names in parallel, capped at MAX_CONCURRENT_REQUESTS threads
-    let mut results: Vec<(String, Result<Availability, CheckError>)> =
-        Vec::with_capacity(names.len());
-    for chunk in names.chunks(MAX_CONCURRENT_REQUESTS) {
-        let chunk_results: Vec<_> = std::thread::scope(|s| {
-            let handles: Vec<_> = chunk
-                .iter()
-                .map(|name| {
-                    let client = &client;
-                    s.spawn(move || (name.clone(), check_name(client, name)))
-                })
-                .collect();
-            handles
+    // Expand each name into its affix variants, skipping any that collide
+    // canonically with an input or an earlier variant
+    let mut variant_names = std::collections::HashMap::new();
+    if let Some(affixes) = cli.affixes() {
+        for name in &names {
+            let expanded: Vec<String> = variants(name, &affixes)
                 .into_iter()
-                .zip(chunk)
-                .map(|(h, original_name)| {
-                    h.join().unwrap_or_else(|_| {
-                        (
-                            original_name.clone(),
-                            Err(CheckError::Internal("thread panic".into())),
-                        )
-                    })
-                })
-                .collect()
-        });
-        results.extend(chunk_results);
+                .filter(|variant| seen.insert(canon_crate_name(variant)))
+                .collect();
+            variant_names.insert(name.clone(), expanded);
+        }
+    }
+
+    // Aliases from cargo configuration shadow `cargo-*` plugins
+    let mut aliases = std::collections::HashMap::new();
+    if !cli.no_subcommand_check && names.iter().any(|n| n.starts_with(PLUGIN_PREFIX)) {
+        let cwd = std::env::current_dir().unwrap_or_default();
+        match cargo_aliases(&cwd) {
+            Ok(found) => aliases = found,
+            Err(e) if !cli.quiet => eprintln!("warning: skipping cargo aliases: {e}"),
+            Err(_) => {}
+        }
+    }
+
+    let mut results = check_all(&client, &names);
+
+    // Score available names; with --sort score or --top, the best come first
+    let scores: std::collections::HashMap<String, Score> = results
+        .iter()
+        .filter(|(_, result)| result_is_available(result))
+        .map(|(name, _)| (name.clone(), score(name)))
+        .collect();
+    let by_score = cli.sort == SortOrder::Score || cli.top.is_some();
+    if by_score {
+        // Stable, so ties and unavailable names keep their input order
+        results.sort_by_key(|(name, _)| std::cmp::Reverse(scores.get(name).map(|s| s.total)));
+    }
+
+    let all_variants: Vec<String> = names
+        .iter()
+        .filter_map(|name| variant_names.get(name))
+        .flatten()
+        .cloned()
+        .collect();
+    let variant_results: std::collections::HashMap<String, CheckResult> =
+        check_all(&client, &all_variants).into_iter().collect();
+
+    // Repair invalid names and check the repaired spelling as well
+    let mut fixes = std::collections::HashMap::new();
+    if cli.suggest_fix {
+        let (originals, repaired): (Vec<&String>, Vec<String>) = results
+            .iter()
+            .filter(|(_, result)| {
+                matches!(
+                    result,
+                    Err(CheckError::InvalidName(_) | CheckError::Lookalike(_))
+                )
+            })
+            .filter_map(|(name, _)| suggest_fix(name).map(|fixed| (name, fixed)))
+            .unzip();
+        fixes.extend(originals.into_iter().zip(check_all(&client, &repaired)));
+    }
+
+    // Count reverse dependencies of taken names: many dependents means a
+    // transfer is unlikely to be worth asking for
+    let mut rdeps = std::collections::HashMap::new();
+    if cli.rdeps || cli.entrenched.is_some() {
+        let taken: Vec<String> = results
+            .iter()
+            .filter(|(_, result)| result.as_ref().is_ok_and(|r| r.collision.is_some()))
+            .map(|(name, _)| name.clone())
+            .collect();
+        rdeps.extend(run_all(&taken, |name| {
+            reverse_dependency_count(&client, name)
+        }));
     }
 
+    // Search for existing crates around each available name's stem
+    let mut namespaces = std::collections::HashMap::new();
+    if cli.crowded {
+        let available: Vec<String> = results
+            .iter()
+            .filter(|(_, result)| result_is_available(result))
+            .map(|(name, _)| name.clone())
+            .collect();
+        namespaces.extend(run_all(&available, |name| related_crates(&client, name)));
+    }
+
+    let top: Option<std::collections::HashSet<String>> = cli.top.map(|n| {
+        results
+            .iter()
+            .filter(|(name, _)| scores.contains_key(name))
+            .take(n)
+            .map(|(name, _)| name.clone())
+            .collect()
+    });
+
+    let printer = Printer {
+        json: cli.json,
+        available_only: cli.available_only,
+        details: cli.details,
+        by_score,
+        no_similar: cli.no_similar,
+        no_subcommand_check: cli.no_subcommand_check,
+        classify: cli.classify_taken.then_some(classify_options),
+        lint_config,
+        entrenched: cli.entrenched,
+        distros: cli.distros.clone(),
+        packages,
+        aliases,
+        bin_check,
+        installs: if bin_check {
+            Installs::load()
+        } else {
+            Installs::default()
+        },
+        manifest,
+        search_path,
+        fixed_from,
+        expanded_from,
+        fixes: fixes
+            .into_iter()
+            .map(|(name, fix)| (name.clone(), fix))
+            .collect(),
+        variant_names,
+        variant_results,
+        rdeps,
+        namespaces,
+        scores,
+    };
+
     let mut any_unavailable = false;
     let mut error_count: usize = 0;
+    // Failed lookups beyond the check itself: the name's availability is
+    // still known, so these are warned about but don't change the exit code
+    let (mut rdep_failures, mut namespace_failures) = (0usize, 0usize);
 
     for (name, result) in &results {
-        let is_available = matches!(result, Ok(Availability::Available));
+        let findings = printer.findings(name, result);
+        // A denied lint makes an otherwise available name count as unavailable
+        let is_available = result_is_available(result) && !any_denied(&findings);
         // Network/internal errors mean we couldn't determine availability.
         // InvalidName is deterministic -- the name is definitively unavailable.
         let is_network_error = matches!(
@@ -162,55 +2126,27 @@ fn main() -> ExitCode {
             Err(CheckError::IndexLookup(_) | CheckError::Internal(_))
         );
 
-        if !is_available && !is_network_error {
+        // Names you already own don't block: re-checking your own workspace passes
+        let is_owned = matches!(result, Ok(r) if r.availability == Availability::Owned);
+
+        if !is_available && !is_owned && !is_network_error {
             any_unavailable = true;
         }
 
         if is_network_error {
             error_count += 1;
         }
-
-        if cli.quiet {
-            continue;
+        if matches!(printer.rdeps.get(name), Some(Err(_))) {
+            rdep_failures += 1;
         }
-
-        if cli.json {
-            let json_result = match result {
-                Ok(a) => JsonResult {
-                    name: name.clone(),
-                    status: a.to_string(),
-                    error: None,
-                },
-                Err(CheckError::InvalidName(e)) => JsonResult {
-                    name: name.clone(),
-                    status: "invalid".to_string(),
-                    error: Some(e.to_string()),
-                },
-                Err(e) => JsonResult {
-                    name: name.clone(),
-                    status: "error".to_string(),
-                    error: Some(e.to_string()),
-                },
-            };
-            println!(
-                "{}",
-                serde_json::to_string(&json_result).expect("JSON serialization should not fail")
-            );
-            continue;
+        if matches!(printer.namespaces.get(name), Some(Err(_))) {
+            namespace_failures += 1;
         }
 
-        // --available-only hides taken/reserved/invalid but always shows errors
-        if cli.available_only && !is_available && !is_network_error {
+        if cli.quiet || top.as_ref().is_some_and(|top| !top.contains(name)) {
             continue;
         }
-
-        let status_str = match result {
-            Ok(a) => a.to_string(),
-            Err(e) => e.to_string(),
-        };
-        let sanitized_name = sanitize(name);
-        let sanitized_status = sanitize(&status_str);
-        println!("{sanitized_name}\t{sanitized_status}");
+        printer.print(name, result, &findings);
     }
 
     if error_count > 0 && !cli.quiet {
@@ -219,6 +2155,18 @@ fn main() -> ExitCode {
             if error_count == 1 { "" } else { "s" }
         );
     }
+    if rdep_failures > 0 && !cli.quiet {
+        eprintln!(
+            "warning: reverse dependencies of {rdep_failures} name{} could not be counted (network error)",
+            if rdep_failures == 1 { "" } else { "s" }
+        );
+    }
+    if namespace_failures > 0 && !cli.quiet {
+        eprintln!(
+            "warning: related crates of {namespace_failures} name{} could not be searched (network error)",
+            if namespace_failures == 1 { "" } else { "s" }
+        );
+    }
 
     if error_count > 0 {
         ExitCode::from(3)
@@ -0,0 +1,207 @@
+//! Crowded-namespace warnings for available names.
+//!
+//! `my-tokio` can be free while a dozen `tokio-*` crates already exist, and
+//! users will still confuse them. [`related_crates`] picks the main stem of a
+//! name, searches crates.io for it, and keeps the results whose names contain
+//! the stem as a whole `-`/`_`-separated word.
+
+use serde::{Deserialize, Serialize};
+
+use crate::check::{CheckError, Client, canon_crate_name};
+
+/// Words that qualify a name rather than identify it (canonical form), so they
+/// are never chosen as the stem when anything else is left.
+const FILLER_WORDS: &[&str] = &[
+    "my", "the", "rs", "rust", "lib", "core", "util", "utils", "ext", "extra", "helpers", "derive",
+    "macros", "sys", "simple", "tiny", "mini",
+];
+
+/// How many search results are inspected. Counts are capped at this.
+const SEARCH_PAGE_SIZE: usize = 100;
+
+/// How many related crates are kept, most downloaded first.
+const TOP_MATCHES: usize = 5;
+
+/// A namespace with at least this many related crates counts as crowded.
+const CROWDED_THRESHOLD: usize = 3;
+
+/// An existing crate that shares a name's stem.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[non_exhaustive]
+pub struct RelatedCrate {
+    /// The crate's name, spelled as published.
+    pub name: String,
+    /// All-time downloads.
+    #[serde(default)]
+    pub downloads: u64,
+    /// The crate's description, if it has one.
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+/// The existing crates around a name's stem.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[non_exhaustive]
+pub struct Namespace {
+    /// The stem that was searched for (canonical form).
+    pub stem: String,
+    /// How many related crates were found (at most 100).
+    pub total: usize,
+    /// The most downloaded related crates, most downloaded first.
+    pub top: Vec<RelatedCrate>,
+}
+
+impl Namespace {
+    /// Whether enough related crates exist that a new one would be easy to
+    /// confuse with them.
+    #[must_use]
+    pub fn is_crowded(&self) -> bool {
+        self.total >= CROWDED_THRESHOLD
+    }
+}
+
+/// Response body of `GET /api/v1/crates?q=...` (only the fields we use).
+#[derive(Deserialize)]
+struct SearchResponse {
+    crates: Vec<RelatedCrate>,
+}
+
+/// The main stem of a name: its longest `-`/`_`-separated word that is not a
+/// filler word like `my` or `rs`.
+///
+/// ```
+/// use cargo_avail::namespace::stem;
+///
+/// assert_eq!(stem("my-tokio"), "tokio");
+/// assert_eq!(stem("serde_json_ext"), "serde");
+/// assert_eq!(stem("rust"), "rust");
+/// ```
+#[must_use]
+pub fn stem(name: &str) -> String {
+    let canonical = canon_crate_name(name);
+    let words: Vec<&str> = canonical.split('_').filter(|w| !w.is_empty()).collect();
+    // `max_by_key` returns the last maximum, so search back to front to
+    // prefer the first of equally long words
+    let longest = |skip_filler: bool| {
+        words
+            .iter()
+            .rev()
+            .filter(|w| !(skip_filler && FILLER_WORDS.contains(w)))
+            .max_by_key(|w| w.len())
+            .copied()
+    };
+    longest(true)
+        .or_else(|| longest(false))
+        .unwrap_or(&canonical)
+        .to_string()
+}
+
+/// Whether `name` contains `stem` as a whole word.
+fn shares_stem(name: &str, stem: &str) -> bool {
+    canon_crate_name(name).split('_').any(|word| word == stem)
+}
+
+/// Search crates.io for existing crates sharing the main stem of `name`.
+///
+/// # Errors
+///
+/// Returns [`CheckError::IndexLookup`] if the search API cannot be queried.
+///
+/// # Example
+///
+/// ```no_run
+/// use cargo_avail::check::Client;
+/// use cargo_avail::namespace::related_crates;
+///
+/// let namespace = related_crates(&Client::new(), "my-tokio").unwrap();
+/// if namespace.is_crowded() {
+///     println!("{} crates around `{}`", namespace.total, namespace.stem);
+/// }
+/// ```
+pub fn related_crates(client: &Client, name: &str) -> Result<Namespace, CheckError> {
+    let stem = stem(name);
+    // Stems are canonical crate-name words, so they need no URL encoding
+    let url = format!(
+        "https://crates.io/api/v1/crates?q={stem}&sort=downloads&per_page={SEARCH_PAGE_SIZE}"
+    );
+    let response: SearchResponse = client.get_json(&url, None)?;
+    Ok(namespace(stem, response.crates))
+}
+
+fn namespace(stem: String, results: Vec<RelatedCrate>) -> Namespace {
+    let mut related: Vec<RelatedCrate> = results
+        .into_iter()
+        .filter(|c| shares_stem(&c.name, &stem))
+        .collect();
+    related.sort_by(|a, b| b.downloads.cmp(&a.downloads));
+    let total = related.len();
+    related.truncate(TOP_MATCHES);
+    Namespace {
+        stem,
+        total,
+        top: related,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stem_skips_filler_words() {
+        assert_eq!(stem("my-tokio"), "tokio");
+        assert_eq!(stem("tokio-util"), "tokio");
+        assert_eq!(stem("rust-lib"), "rust");
+    }
+
+    #[test]
+    fn stem_prefers_the_first_longest_word() {
+        assert_eq!(stem("axum-login"), "login");
+        assert_eq!(stem("serde-yaml"), "serde");
+        assert_eq!(stem("Foo_BAR"), "foo");
+    }
+
+    #[test]
+    fn related_crates_share_a_whole_word() {
+        assert!(shares_stem("tokio-util", "tokio"));
+        assert!(shares_stem("async_tokio", "tokio"));
+        assert!(shares_stem("tokio", "tokio"));
+        assert!(!shares_stem("tokiox", "tokio"));
+    }
+
+    #[test]
+    fn search_results_filtered_and_ranked() {
+        let response: SearchResponse = serde_json::from_str(
+            r#"{"crates":[
+                {"name":"tokio-util","downloads":300,"description":"Utilities"},
+                {"name":"mio","downloads":900,"description":"Mentions tokio"},
+                {"name":"tokio","downloads":1000,"description":null},
+                {"name":"console-tokio","downloads":5},
+                {"name":"tokio-stream","downloads":200}
+            ],"meta":{"total":5}}"#,
+        )
+        .unwrap();
+        let namespace = namespace("tokio".to_string(), response.crates);
+        assert_eq!(namespace.total, 4);
+        assert!(namespace.is_crowded());
+        let names: Vec<&str> = namespace.top.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(
+            names,
+            ["tokio", "tokio-util", "tokio-stream", "console-tokio"]
+        );
+    }
+
+    #[test]
+    fn sparse_namespace_is_not_crowded() {
+        let namespace = namespace(
+            "zzyzx".to_string(),
+            vec![RelatedCrate {
+                name: "zzyzx".to_string(),
+                downloads: 1,
+                description: None,
+            }],
+        );
+        assert_eq!(namespace.total, 1);
+        assert!(!namespace.is_crowded());
+    }
+}
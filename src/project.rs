@@ -0,0 +1,365 @@
+//! Salient terms from an existing project, for naming it.
+//!
+//! A new crate usually already says what it does: in the `description` and
+//! `keywords` of its `Cargo.toml` and in the headings of its README.
+//! [`Project::terms`] picks out the words that say the most, offline, by
+//! TF-IDF: a word scores higher the more often the project uses it, and lower
+//! the more crates in a bundled background corpus of crate descriptions use
+//! it too. Words like `fast`, `library` or `rust` describe everything and so
+//! describe nothing.
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+/// How many terms are taken from a project by default.
+pub const DEFAULT_TERMS: usize = 4;
+
+/// Words shorter than this are dropped.
+const MIN_TERM_LENGTH: usize = 3;
+
+/// How many times a word counts in each part of the project. Keywords are
+/// chosen to describe the crate; headings are mostly structure.
+const KEYWORD_WEIGHT: f64 = 3.0;
+const DESCRIPTION_WEIGHT: f64 = 2.0;
+const HEADING_WEIGHT: f64 = 1.0;
+
+/// README files tried when the manifest names none.
+const README_NAMES: &[&str] = &["README.md", "README.txt", "README"];
+
+/// English function words, and the words of the headings most READMEs share,
+/// separated by whitespace.
+const STOPWORDS: &str = "
+    about all also and any are but can for from has have how into its just not
+    only our out than that the their then there they this use using was what
+    when whether which will with you your
+    api changelog contributing documentation example examples faq feature
+    features getting install installation license overview quick start started
+    usage
+";
+
+/// How many background descriptions there are, and how many of them use each
+/// word.
+static BACKGROUND: LazyLock<(usize, HashMap<String, usize>)> = LazyLock::new(|| {
+    let descriptions: Vec<&str> = include_str!("data/background-descriptions.txt")
+        .lines()
+        .filter(|line| !line.trim().is_empty() && !line.starts_with('#'))
+        .collect();
+    let mut frequency = HashMap::new();
+    for description in &descriptions {
+        for word in tokens(description).collect::<HashSet<_>>() {
+            *frequency.entry(word).or_default() += 1;
+        }
+    }
+    (descriptions.len(), frequency)
+});
+
+/// Errors that can occur while reading a project.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum ProjectError {
+    /// The manifest or README could not be read.
+    Io(PathBuf, std::io::Error),
+    /// The manifest is not valid TOML.
+    Parse(PathBuf, toml::de::Error),
+}
+
+impl fmt::Display for ProjectError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(path, e) => write!(f, "reading {}: {e}", path.display()),
+            Self::Parse(path, e) => write!(f, "parsing {}: {e}", path.display()),
+        }
+    }
+}
+
+impl std::error::Error for ProjectError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(_, e) => Some(e),
+            Self::Parse(_, e) => Some(e),
+        }
+    }
+}
+
+/// The parts of a project that describe what it does.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Project {
+    /// The package name, whose words are never terms: it is the name being
+    /// replaced.
+    pub name: Option<String>,
+    /// The package description.
+    pub description: Option<String>,
+    /// The package keywords.
+    pub keywords: Vec<String>,
+    /// The README headings, without their `#` markers.
+    pub headings: Vec<String>,
+}
+
+impl Project {
+    /// Read the project in `dir`: its `Cargo.toml`, and the README the
+    /// manifest names (or `README.md`, `README.txt` or `README` if it names
+    /// none). A missing README is not an error.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ProjectError`] if the manifest is missing or not valid TOML,
+    /// or if the README exists but cannot be read.
+    pub fn load(dir: &Path) -> Result<Self, ProjectError> {
+        let manifest_path = dir.join("Cargo.toml");
+        let manifest: toml::Table = std::fs::read_to_string(&manifest_path)
+            .map_err(|e| ProjectError::Io(manifest_path.clone(), e))?
+            .parse()
+            .map_err(|e| ProjectError::Parse(manifest_path, e))?;
+        let mut project = Self::from_manifest(&manifest);
+        let package = manifest.get("package");
+        let readme_names: Vec<&str> = match package.and_then(|p| p.get("readme")) {
+            Some(toml::Value::String(path)) => vec![path.as_str()],
+            Some(toml::Value::Boolean(false)) => Vec::new(),
+            _ => README_NAMES.to_vec(),
+        };
+        for name in readme_names {
+            let path = dir.join(name);
+            match std::fs::read_to_string(&path) {
+                Ok(text) => {
+                    project.headings = headings(&text);
+                    break;
+                }
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+                Err(e) => return Err(ProjectError::Io(path, e)),
+            }
+        }
+        Ok(project)
+    }
+
+    /// The name, description and keywords in a parsed manifest's `[package]`
+    /// table. Fields inherited from a workspace are skipped.
+    #[must_use]
+    pub fn from_manifest(manifest: &toml::Table) -> Self {
+        let package = manifest.get("package");
+        let string = |key| {
+            package
+                .and_then(|p| p.get(key))
+                .and_then(toml::Value::as_str)
+                .map(str::to_string)
+        };
+        let keywords = package
+            .and_then(|p| p.get("keywords"))
+            .and_then(toml::Value::as_array)
+            .map(|keywords| {
+                keywords
+                    .iter()
+                    .filter_map(|k| k.as_str().map(str::to_string))
+                    .collect()
+            })
+            .unwrap_or_default();
+        Self {
+            name: string("name"),
+            description: string("description"),
+            keywords,
+            headings: Vec::new(),
+        }
+    }
+
+    /// Up to `count` of the project's most salient words, best first. Ties
+    /// keep the word seen first, reading keywords, then the description, then
+    /// the headings.
+    ///
+    /// ```
+    /// use cargo_avail::project::Project;
+    ///
+    /// let manifest = r#"
+    ///     [package]
+    ///     name = "mycrate"
+    ///     description = "A fast library for parsing GeoJSON geometry"
+    ///     keywords = ["geojson", "parser"]
+    /// "#;
+    /// let project = Project::from_manifest(&manifest.parse().unwrap());
+    /// assert_eq!(project.terms(3), ["geojson", "parser", "geometry"]);
+    /// ```
+    #[must_use]
+    pub fn terms(&self, count: usize) -> Vec<String> {
+        let excluded: HashSet<String> = self.name.iter().flat_map(|n| tokens(n)).collect();
+        let sources = self
+            .keywords
+            .iter()
+            .map(|k| (k.as_str(), KEYWORD_WEIGHT))
+            .chain(
+                self.description
+                    .iter()
+                    .map(|d| (d.as_str(), DESCRIPTION_WEIGHT)),
+            )
+            .chain(self.headings.iter().map(|h| (h.as_str(), HEADING_WEIGHT)));
+        let mut order = Vec::new();
+        let mut frequency: HashMap<String, f64> = HashMap::new();
+        for (text, weight) in sources {
+            for word in tokens(text) {
+                if STOPWORDS.split_whitespace().any(|stop| stop == word) || excluded.contains(&word)
+                {
+                    continue;
+                }
+                let seen = frequency.entry(word.clone()).or_insert_with(|| {
+                    order.push(word);
+                    0.0
+                });
+                *seen += weight;
+            }
+        }
+        let mut scored: Vec<(String, f64)> = order
+            .into_iter()
+            .map(|word| {
+                let score = frequency[&word] * inverse_frequency(&word);
+                (word, score)
+            })
+            .collect();
+        // Stable, so ties keep the order the words were seen in
+        scored.sort_by(|a, b| b.1.total_cmp(&a.1));
+        scored
+            .into_iter()
+            .take(count)
+            .map(|(word, _)| word)
+            .collect()
+    }
+}
+
+/// How rare a word is in the background corpus: `ln((n + 1) / (df + 1)) + 1`,
+/// smoothed so unseen words score highest and no word scores zero.
+fn inverse_frequency(word: &str) -> f64 {
+    let (total, frequency) = &*BACKGROUND;
+    let count = |n: usize| f64::from(u32::try_from(n).unwrap_or(u32::MAX));
+    let df = frequency.get(word).copied().unwrap_or(0);
+    ((count(*total) + 1.0) / (count(df) + 1.0)).ln() + 1.0
+}
+
+/// The lowercase words of `text` that can be terms: runs of ASCII letters and
+/// digits starting with a letter, at least [`MIN_TERM_LENGTH`] long.
+fn tokens(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|w| w.len() >= MIN_TERM_LENGTH && w.starts_with(|c: char| c.is_ascii_alphabetic()))
+        .map(str::to_ascii_lowercase)
+}
+
+/// The ATX headings of a Markdown document, outside code blocks, with link
+/// targets and images (usually badges) removed.
+fn headings(markdown: &str) -> Vec<String> {
+    let mut fence: Option<&str> = None;
+    let mut headings = Vec::new();
+    for line in markdown.lines() {
+        let line = line.trim_start();
+        if let Some(marker) = fence {
+            if line.starts_with(marker) {
+                fence = None;
+            }
+            continue;
+        }
+        if let Some(marker) = ["```", "~~~"].into_iter().find(|m| line.starts_with(m)) {
+            fence = Some(marker);
+            continue;
+        }
+        let text = line.trim_start_matches('#');
+        if text.len() == line.len() || !(text.is_empty() || text.starts_with(' ')) {
+            continue;
+        }
+        let text = strip_links(text.trim().trim_end_matches('#'));
+        if !text.trim().is_empty() {
+            headings.push(text.trim().to_string());
+        }
+    }
+    headings
+}
+
+/// Drop the `(target)` of each Markdown link and whole `![image](target)`s.
+fn strip_links(text: &str) -> String {
+    let mut out = String::new();
+    let mut rest = text;
+    while let Some(close) = rest.find("](") {
+        let Some(end) = rest[close..].find(')') else {
+            break;
+        };
+        let (before, after) = (&rest[..close], &rest[close + end + 1..]);
+        match before.rfind("![") {
+            Some(image) => out.push_str(&before[..image]),
+            None => out.push_str(before),
+        }
+        rest = after;
+    }
+    out.push_str(rest);
+    out.replace(['[', ']'], "")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn headings_skip_code_blocks_and_badges() {
+        let readme = "\
+# geoparse [![crates.io](https://img.shields.io/crates/v/x.svg)](https://crates.io)
+
+Some text.
+
+```sh
+# not a heading
+```
+
+## Parsing [GeoJSON](https://geojson.org) ##
+#hashtag
+~~~
+## also not a heading
+~~~
+### Usage
+";
+        assert_eq!(headings(readme), ["geoparse", "Parsing GeoJSON", "Usage"]);
+    }
+
+    #[test]
+    fn tokens_are_lowercase_words() {
+        let words: Vec<String> = tokens("Fast HTTP/2 client, in 100% Rust: h2c utf8").collect();
+        assert_eq!(words, ["fast", "http", "client", "rust", "h2c", "utf8"]);
+    }
+
+    #[test]
+    fn common_words_are_less_salient() {
+        assert!(inverse_frequency("library") < inverse_frequency("geojson"));
+        assert!(inverse_frequency("rust") < inverse_frequency("tokenizer"));
+    }
+
+    #[test]
+    fn keywords_outweigh_headings() {
+        let project = Project {
+            keywords: vec!["raster".to_string()],
+            headings: vec!["Tiles".to_string(), "Tiles and more tiles".to_string()],
+            ..Project::default()
+        };
+        assert_eq!(project.terms(2), ["raster", "tiles"]);
+    }
+
+    #[test]
+    fn stopwords_and_the_package_name_are_not_terms() {
+        let project = Project {
+            name: Some("acme-geo".to_string()),
+            description: Some("The acme geo toolkit for your maps".to_string()),
+            headings: vec!["Installation".to_string(), "License".to_string()],
+            ..Project::default()
+        };
+        // `toolkit` turns up in the background corpus, `maps` does not
+        assert_eq!(project.terms(DEFAULT_TERMS), ["maps", "toolkit"]);
+    }
+
+    #[test]
+    fn workspace_fields_are_skipped() {
+        let manifest: toml::Table = r#"
+            [package]
+            name = "x"
+            description.workspace = true
+            keywords = ["wasm", 3]
+        "#
+        .parse()
+        .unwrap();
+        let project = Project::from_manifest(&manifest);
+        assert_eq!(project.description, None);
+        assert_eq!(project.keywords, ["wasm"]);
+    }
+}
@@ -0,0 +1,303 @@
+//! Pronounceable random names.
+//!
+//! For internal codenames any short, sayable, unused name will do.
+//! [`NameModel`] is a character Markov chain: it learns which letter tends to
+//! follow each pair of letters in a corpus and strings letters together the
+//! same way, so its names read like words without being words. The bundled
+//! model is trained on common English words and the words of popular crate
+//! names.
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::str::FromStr;
+use std::sync::LazyLock;
+
+use crate::check::is_reserved;
+use crate::check::validation::validate_crate_name;
+use crate::rng::SplitMix64;
+
+/// The name lengths generated when none are configured.
+pub const DEFAULT_LENGTHS: Lengths = Lengths { min: 5, max: 8 };
+
+/// Random walks tried for each name before giving up, so impossible length
+/// ranges end the sequence instead of looping forever.
+const MAX_ATTEMPTS: usize = 10_000;
+
+/// Marks the start and end of a word in the transition table.
+const BOUNDARY: u8 = b'^';
+
+/// Corpus words shorter than this teach the model too little to be worth it.
+const MIN_CORPUS_WORD: usize = 3;
+
+static BUNDLED: LazyLock<NameModel> = LazyLock::new(|| {
+    let crate_words = include_str!("data/popular-crates.txt")
+        .lines()
+        .filter(|line| !line.starts_with('#'));
+    let english = include_str!("data/english-words.txt")
+        .lines()
+        .filter(|line| !line.starts_with('#'));
+    NameModel::train(english.chain(crate_words))
+});
+
+/// An inclusive range of name lengths, written `5..8` (or `5..=8`, or `6`
+/// for a single length).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Lengths {
+    /// The shortest length allowed.
+    pub min: usize,
+    /// The longest length allowed.
+    pub max: usize,
+}
+
+impl fmt::Display for Lengths {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}..{}", self.min, self.max)
+    }
+}
+
+impl FromStr for Lengths {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("invalid length range `{s}` (expected something like `5..8`)");
+        let (min, max) = match s.split_once("..") {
+            Some((min, max)) => (min, max.strip_prefix('=').unwrap_or(max)),
+            None => (s, s),
+        };
+        let min: usize = min.trim().parse().map_err(|_| invalid())?;
+        let max: usize = max.trim().parse().map_err(|_| invalid())?;
+        if min < 2 || min > max {
+            return Err(invalid());
+        }
+        Ok(Self { min, max })
+    }
+}
+
+/// A character Markov chain over lowercase ASCII letters, trained on a corpus.
+#[derive(Debug, Clone)]
+pub struct NameModel {
+    /// For each pair of preceding letters, the letters seen next and how
+    /// often, in byte order. [`BOUNDARY`] stands for the start or end of a
+    /// word.
+    transitions: HashMap<[u8; 2], Vec<(u8, u64)>>,
+    /// The corpus words, which are never generated.
+    corpus: HashSet<String>,
+}
+
+impl NameModel {
+    /// Train a model on `words`.
+    ///
+    /// Words are lowercased and split at anything other than ASCII letters;
+    /// pieces shorter than three letters are ignored.
+    pub fn train<I, S>(words: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut counts: HashMap<[u8; 2], HashMap<u8, u64>> = HashMap::new();
+        let mut corpus = HashSet::new();
+        for word in words {
+            let word = word.as_ref().to_ascii_lowercase();
+            for piece in word.split(|c: char| !c.is_ascii_lowercase()) {
+                if piece.len() < MIN_CORPUS_WORD || !corpus.insert(piece.to_string()) {
+                    continue;
+                }
+                let mut context = [BOUNDARY, BOUNDARY];
+                for next in piece.bytes().chain([BOUNDARY]) {
+                    *counts.entry(context).or_default().entry(next).or_default() += 1;
+                    context = [context[1], next];
+                }
+            }
+        }
+        let transitions = counts
+            .into_iter()
+            .map(|(context, next)| {
+                let mut next: Vec<(u8, u64)> = next.into_iter().collect();
+                next.sort_unstable();
+                (context, next)
+            })
+            .collect();
+        Self {
+            transitions,
+            corpus,
+        }
+    }
+
+    /// The model trained on the bundled corpus.
+    #[must_use]
+    pub fn bundled() -> &'static Self {
+        &BUNDLED
+    }
+
+    /// An endless, reproducible sequence of distinct names whose lengths
+    /// fall within `lengths`.
+    ///
+    /// Names that fail crates.io validation, are reserved, are hard to say
+    /// (three vowels or four consonants in a row) or are words from the
+    /// corpus are skipped. The sequence ends early only if no new name turns
+    /// up after many attempts, as with an impossible length range.
+    ///
+    /// ```
+    /// use cargo_avail::random::{NameModel, DEFAULT_LENGTHS};
+    ///
+    /// let names: Vec<String> = NameModel::bundled().names(DEFAULT_LENGTHS, 42).take(5).collect();
+    /// assert_eq!(names.len(), 5);
+    /// assert!(names.iter().all(|n| (5..=8).contains(&n.len())));
+    /// ```
+    #[must_use]
+    pub fn names(&self, lengths: Lengths, seed: u64) -> Names<'_> {
+        Names {
+            model: self,
+            lengths,
+            rng: SplitMix64(seed),
+            seen: HashSet::new(),
+        }
+    }
+
+    /// One random walk through the chain, or `None` if it runs past `max`
+    /// letters.
+    fn walk(&self, rng: &mut SplitMix64, max: usize) -> Option<String> {
+        let mut name = String::new();
+        let mut context = [BOUNDARY, BOUNDARY];
+        loop {
+            let next = self.transitions.get(&context)?;
+            let total = next.iter().map(|&(_, count)| count).sum();
+            let mut pick = rng.below(total);
+            let &(letter, _) = next.iter().find(|&&(_, count)| {
+                if pick < count {
+                    true
+                } else {
+                    pick -= count;
+                    false
+                }
+            })?;
+            if letter == BOUNDARY {
+                return Some(name);
+            }
+            if name.len() == max {
+                return None;
+            }
+            name.push(char::from(letter));
+            context = [context[1], letter];
+        }
+    }
+
+    /// Whether a generated name is usable.
+    fn accepts(&self, name: &str, lengths: Lengths) -> bool {
+        (lengths.min..=lengths.max).contains(&name.len())
+            && is_pronounceable(name)
+            && !self.corpus.contains(name)
+            && validate_crate_name(name).is_ok()
+            && !is_reserved(name)
+    }
+}
+
+/// Whether a name avoids the letter runs that make words hard to say: three
+/// vowels or four consonants in a row.
+fn is_pronounceable(name: &str) -> bool {
+    let is_vowel = |c: u8| matches!(c, b'a' | b'e' | b'i' | b'o' | b'u' | b'y');
+    let (mut vowels, mut consonants) = (0, 0);
+    for c in name.bytes() {
+        if is_vowel(c) {
+            (vowels, consonants) = (vowels + 1, 0);
+        } else {
+            (vowels, consonants) = (0, consonants + 1);
+        }
+        if vowels >= 3 || consonants >= 4 {
+            return false;
+        }
+    }
+    true
+}
+
+/// The names generated by [`NameModel::names`].
+#[derive(Debug)]
+pub struct Names<'a> {
+    model: &'a NameModel,
+    lengths: Lengths,
+    rng: SplitMix64,
+    seen: HashSet<String>,
+}
+
+impl Iterator for Names<'_> {
+    type Item = String;
+
+    fn next(&mut self) -> Option<String> {
+        for _ in 0..MAX_ATTEMPTS {
+            let Some(name) = self.model.walk(&mut self.rng, self.lengths.max) else {
+                continue;
+            };
+            if self.model.accepts(&name, self.lengths) && self.seen.insert(name.clone()) {
+                return Some(name);
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lengths_parse_as_inclusive_ranges() {
+        assert_eq!("5..8".parse(), Ok(Lengths { min: 5, max: 8 }));
+        assert_eq!("5..=8".parse(), Ok(Lengths { min: 5, max: 8 }));
+        assert_eq!("6".parse(), Ok(Lengths { min: 6, max: 6 }));
+        assert!("8..5".parse::<Lengths>().is_err());
+        assert!("1..4".parse::<Lengths>().is_err());
+        assert!("five".parse::<Lengths>().is_err());
+        assert_eq!(DEFAULT_LENGTHS.to_string().parse(), Ok(DEFAULT_LENGTHS));
+    }
+
+    #[test]
+    fn names_are_reproducible_from_a_seed() {
+        let model = NameModel::bundled();
+        let first: Vec<String> = model.names(DEFAULT_LENGTHS, 7).take(20).collect();
+        let again: Vec<String> = model.names(DEFAULT_LENGTHS, 7).take(20).collect();
+        let other: Vec<String> = model.names(DEFAULT_LENGTHS, 8).take(20).collect();
+        assert_eq!(first, again);
+        assert_ne!(first, other);
+    }
+
+    #[test]
+    fn names_are_new_distinct_and_usable() {
+        let model = NameModel::bundled();
+        let names: Vec<String> = model.names(DEFAULT_LENGTHS, 1).take(200).collect();
+        assert_eq!(names.len(), 200);
+        assert_eq!(names.iter().collect::<HashSet<_>>().len(), 200);
+        for name in &names {
+            assert!((5..=8).contains(&name.len()), "{name}");
+            assert!(name.bytes().all(|c| c.is_ascii_lowercase()), "{name}");
+            assert!(is_pronounceable(name), "{name}");
+            assert!(!model.corpus.contains(name), "{name}");
+        }
+    }
+
+    #[test]
+    fn small_corpus_is_learned_exactly() {
+        // Every transition in `abcd` is forced, and the corpus word itself is
+        // never produced
+        let model = NameModel::train(["abcd"]);
+        assert_eq!(
+            model.transitions[b"bc"],
+            [(b'd', 1)],
+            "{:?}",
+            model.transitions
+        );
+        assert_eq!(model.names(Lengths { min: 2, max: 8 }, 0).next(), None);
+    }
+
+    #[test]
+    fn impossible_lengths_end_the_sequence() {
+        let lengths = Lengths { min: 60, max: 64 };
+        assert_eq!(NameModel::bundled().names(lengths, 0).next(), None);
+    }
+
+    #[test]
+    fn pronounceability_limits_letter_runs() {
+        assert!(is_pronounceable("strander"));
+        assert!(!is_pronounceable("ouai"));
+        assert!(!is_pronounceable("hrtzk"));
+    }
+}
@@ -0,0 +1,250 @@
+//! Repair suggestions for syntactically invalid crate names.
+//!
+//! When a name fails crates.io validation, [`suggest_fix`] proposes the
+//! closest name that passes: lookalike characters are transliterated,
+//! diacritics dropped, punctuation and whitespace turned into separators,
+//! leading digits moved or spelled out, and overlong names truncated at a
+//! separator boundary.
+
+use crate::check::validation::{MAX_NAME_LENGTH, validate_crate_name};
+use crate::{unicode, variants};
+
+/// Latin letters with diacritics, grouped by their ASCII base letter(s).
+const DIACRITICS: &[(&str, &str)] = &[
+    ("ÀÁÂÃÄÅĀĂĄ", "A"),
+    ("àáâãäåāăą", "a"),
+    ("ÇĆĈĊČ", "C"),
+    ("çćĉċč", "c"),
+    ("ÐĎĐ", "D"),
+    ("ðďđ", "d"),
+    ("ÈÉÊËĒĔĖĘĚ", "E"),
+    ("èéêëēĕėęě", "e"),
+    ("ĜĞĠĢ", "G"),
+    ("ĝğġģ", "g"),
+    ("ĤĦ", "H"),
+    ("ĥħ", "h"),
+    ("ÌÍÎÏĨĪĬĮİ", "I"),
+    ("ìíîïĩīĭį", "i"),
+    ("Ĵ", "J"),
+    ("ĵ", "j"),
+    ("Ķ", "K"),
+    ("ķĸ", "k"),
+    ("ĹĻĽĿŁ", "L"),
+    ("ĺļľŀł", "l"),
+    ("ÑŃŅŇŊ", "N"),
+    ("ñńņňŉŋ", "n"),
+    ("ÒÓÔÕÖØŌŎŐ", "O"),
+    ("òóôõöøōŏő", "o"),
+    ("ŔŖŘ", "R"),
+    ("ŕŗř", "r"),
+    ("ŚŜŞŠ", "S"),
+    ("śŝşšſ", "s"),
+    ("ŢŤŦ", "T"),
+    ("ţťŧ", "t"),
+    ("ÙÚÛÜŨŪŬŮŰŲ", "U"),
+    ("ùúûüũūŭůűų", "u"),
+    ("Ŵ", "W"),
+    ("ŵ", "w"),
+    ("ÝŶŸ", "Y"),
+    ("ýÿŷ", "y"),
+    ("ŹŻŽ", "Z"),
+    ("źżž", "z"),
+    ("Æ", "AE"),
+    ("æ", "ae"),
+    ("Ĳ", "IJ"),
+    ("ĳ", "ij"),
+    ("Œ", "OE"),
+    ("œ", "oe"),
+    ("Þ", "TH"),
+    ("þ", "th"),
+    ("ß", "ss"),
+];
+
+const DIGIT_WORDS: [&str; 10] = [
+    "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+/// Suggest the closest valid crate name for an invalid one.
+///
+/// Returns `None` if the name is already valid, or if nothing usable is left
+/// after repair (e.g., the name consists only of punctuation).
+///
+/// ```
+/// use cargo_avail::repair::suggest_fix;
+///
+/// assert_eq!(suggest_fix("foo.bar+baz").as_deref(), Some("foo-bar-baz"));
+/// assert_eq!(suggest_fix("3d-engine").as_deref(), Some("engine-3d"));
+/// assert_eq!(suggest_fix("café").as_deref(), Some("cafe"));
+/// assert_eq!(suggest_fix("serde"), None);
+/// ```
+#[must_use]
+pub fn suggest_fix(name: &str) -> Option<String> {
+    if validate_crate_name(name).is_ok() {
+        return None;
+    }
+
+    let sep = variants::separator(name);
+
+    let mut segments = segments(&strip_diacritics(&unicode::transliterate(name)));
+    if segments.is_empty() {
+        return None;
+    }
+    fix_leading_digits(&mut segments);
+
+    let repaired = truncate(&segments.join(&sep.to_string()), sep);
+    validate_crate_name(&repaired).is_ok().then_some(repaired)
+}
+
+/// Replace Latin letters with diacritics by their ASCII base letters and drop
+/// combining marks.
+fn strip_diacritics(name: &str) -> String {
+    let mut out = String::with_capacity(name.len());
+    for ch in name.chars() {
+        if ('\u{0300}'..='\u{036F}').contains(&ch) {
+            continue;
+        }
+        match DIACRITICS.iter().find(|(chars, _)| chars.contains(ch)) {
+            Some((_, base)) => out.push_str(base),
+            None => out.push(ch),
+        }
+    }
+    out
+}
+
+/// Split a name into runs of ASCII alphanumerics. Everything else (`.`, `+`,
+/// whitespace, separators, and characters with no ASCII equivalent) acts as a
+/// boundary.
+fn segments(name: &str) -> Vec<String> {
+    name.split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Move an all-digit or digit-led first segment to the end (`3d-engine` becomes
+/// `engine-3d`). A lone segment has nowhere to go, so its leading digits are
+/// spelled out instead (`2fa` becomes `two-fa`).
+fn fix_leading_digits(segments: &mut Vec<String>) {
+    if !segments[0].starts_with(|c: char| c.is_ascii_digit()) {
+        return;
+    }
+    if segments.len() > 1 && segments.iter().any(|s| s.starts_with(char::is_alphabetic)) {
+        while segments[0].starts_with(|c: char| c.is_ascii_digit()) {
+            segments.rotate_left(1);
+        }
+        return;
+    }
+    let first = segments.remove(0);
+    let digits_end = first
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(first.len());
+    let (digits, rest) = first.split_at(digits_end);
+    let mut spelled: Vec<String> = digits
+        .bytes()
+        .map(|b| DIGIT_WORDS[usize::from(b - b'0')].to_string())
+        .collect();
+    if !rest.is_empty() {
+        spelled.push(rest.to_string());
+    }
+    segments.splice(0..0, spelled);
+}
+
+/// Truncate to [`MAX_NAME_LENGTH`], preferring to cut at a separator so no
+/// word is left half-finished.
+fn truncate(name: &str, sep: char) -> String {
+    if name.len() <= MAX_NAME_LENGTH {
+        return name.to_string();
+    }
+    let head = &name[..MAX_NAME_LENGTH];
+    if name[MAX_NAME_LENGTH..].starts_with(sep) {
+        return head.to_string();
+    }
+    match head.rfind(sep) {
+        Some(cut) if cut > 0 => head[..cut].to_string(),
+        _ => head.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn punctuation_and_spaces_become_separators() {
+        assert_eq!(suggest_fix("my crate.rs").as_deref(), Some("my-crate-rs"));
+        assert_eq!(suggest_fix("c++ bindings").as_deref(), Some("c-bindings"));
+    }
+
+    #[test]
+    fn underscore_style_is_preserved() {
+        assert_eq!(suggest_fix("foo_bar.baz").as_deref(), Some("foo_bar_baz"));
+    }
+
+    #[test]
+    fn leading_separators_are_stripped() {
+        assert_eq!(suggest_fix("_private").as_deref(), Some("private"));
+        assert_eq!(suggest_fix("---test").as_deref(), Some("test"));
+    }
+
+    #[test]
+    fn lone_leading_digits_are_spelled_out() {
+        assert_eq!(suggest_fix("2fa").as_deref(), Some("two-fa"));
+        assert_eq!(suggest_fix("42").as_deref(), Some("four-two"));
+    }
+
+    #[test]
+    fn leading_numeric_segment_moves_to_end() {
+        assert_eq!(suggest_fix("3d-engine").as_deref(), Some("engine-3d"));
+        assert_eq!(suggest_fix("1-2-go").as_deref(), Some("go-1-2"));
+    }
+
+    #[test]
+    fn diacritics_are_dropped() {
+        assert_eq!(suggest_fix("naïve-bäyes").as_deref(), Some("naive-bayes"));
+        assert_eq!(suggest_fix("stra\u{0308}sse").as_deref(), Some("strasse"));
+        assert_eq!(suggest_fix("straße").as_deref(), Some("strasse"));
+    }
+
+    #[test]
+    fn lookalikes_are_transliterated() {
+        assert_eq!(
+            suggest_fix("serde\u{2013}json\u{200B}").as_deref(),
+            Some("serde-json")
+        );
+    }
+
+    #[test]
+    fn too_long_names_are_cut_at_a_separator() {
+        let name = format!("{}-tail", "word-".repeat(13));
+        let fixed = suggest_fix(&name).unwrap();
+        assert!(fixed.len() <= MAX_NAME_LENGTH, "{fixed}");
+        assert!(fixed.ends_with("word"), "{fixed}");
+    }
+
+    #[test]
+    fn too_long_unbroken_name_is_hard_truncated() {
+        let fixed = suggest_fix(&"a".repeat(100)).unwrap();
+        assert_eq!(fixed.len(), MAX_NAME_LENGTH);
+    }
+
+    #[test]
+    fn unrepairable_names_have_no_suggestion() {
+        assert_eq!(suggest_fix(""), None);
+        assert_eq!(suggest_fix("+++"), None);
+        assert_eq!(suggest_fix("\u{4E2D}\u{6587}"), None);
+    }
+
+    mod prop {
+        use super::*;
+        use proptest::prelude::*;
+
+        proptest! {
+            #[test]
+            fn suggestions_are_always_valid(name in "\\PC{0,80}") {
+                if let Some(fixed) = suggest_fix(&name) {
+                    prop_assert!(validate_crate_name(&fixed).is_ok(), "{}", fixed);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,256 @@
+//! Quality scores for available names.
+//!
+//! When 80 of 300 candidates come back available, someone still has to pick
+//! one. [`score`] rates a name from 0 to 100 on the things that make names
+//! pleasant or painful to live with, and keeps the per-factor breakdown so the
+//! rating can be explained:
+//!
+//! - **length**: 4 to 10 characters is comfortable
+//! - **typability**: no same-finger jumps on a QWERTY keyboard, no digits or
+//!   rare letters
+//! - **pronounceability**: no long runs of consonants or vowels
+//! - **dictionary**: a plain English word is hard to search for
+//! - **similarity**: resembling a popular crate invites confusion
+//! - **separators**: each `-` or `_` is one more thing to get wrong, and
+//!   mixing them is worse
+
+use std::collections::HashSet;
+use std::sync::LazyLock;
+
+use serde::Serialize;
+
+use crate::check::canon_crate_name;
+use crate::similar::find_similar;
+
+/// Common English words (lowercase).
+static DICTIONARY: LazyLock<HashSet<&'static str>> = LazyLock::new(|| {
+    include_str!("data/english-words.txt")
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .collect()
+});
+
+/// How much each factor counts towards the total, out of 100.
+const WEIGHTS: Weights = Weights {
+    length: 20,
+    typability: 15,
+    pronounceability: 20,
+    dictionary: 15,
+    similarity: 20,
+    separators: 10,
+};
+
+struct Weights {
+    length: u32,
+    typability: u32,
+    pronounceability: u32,
+    dictionary: u32,
+    similarity: u32,
+    separators: u32,
+}
+
+/// Letters typed by the same finger on a QWERTY keyboard, one group per
+/// finger. Typing two different keys of a group in a row is awkward.
+const FINGERS: &[&str] = &["qaz", "wsx", "edc", "rfvtgb", "yhnujm", "ik", "ol", "p"];
+
+/// Letters that are rare in English and awkward to reach.
+const RARE_LETTERS: &[char] = &['q', 'x', 'z', 'j'];
+
+/// A name's quality score and its breakdown, each from 0 to 100.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[non_exhaustive]
+pub struct Score {
+    /// The weighted total.
+    pub total: u32,
+    /// Comfortable length.
+    pub length: u32,
+    /// Ease of typing.
+    pub typability: u32,
+    /// Ease of saying it.
+    pub pronounceability: u32,
+    /// Not being a plain dictionary word.
+    pub dictionary: u32,
+    /// Not resembling a popular crate.
+    pub similarity: u32,
+    /// Few, consistent separators.
+    pub separators: u32,
+}
+
+/// Score a name.
+///
+/// ```
+/// use cargo_avail::score::score;
+///
+/// let good = score("tokenblend");
+/// assert!(good.total > score("x9-q_zz").total);
+/// // A dictionary word is hard to search for
+/// assert!(score("lantern").dictionary < good.dictionary);
+/// ```
+#[must_use]
+pub fn score(name: &str) -> Score {
+    let canonical = canon_crate_name(name);
+    let length = length_score(name.chars().count());
+    let typability = typability_score(&canonical);
+    let pronounceability = pronounceability_score(&canonical);
+    let dictionary = if DICTIONARY.contains(canonical.replace('_', "").as_str()) {
+        30
+    } else {
+        100
+    };
+    let similarity = if find_similar(name).is_some() {
+        20
+    } else {
+        100
+    };
+    let separators = separator_score(name);
+    let total = (WEIGHTS.length * length
+        + WEIGHTS.typability * typability
+        + WEIGHTS.pronounceability * pronounceability
+        + WEIGHTS.dictionary * dictionary
+        + WEIGHTS.similarity * similarity
+        + WEIGHTS.separators * separators
+        + 50)
+        / 100;
+    Score {
+        total,
+        length,
+        typability,
+        pronounceability,
+        dictionary,
+        similarity,
+        separators,
+    }
+}
+
+/// Subtract `penalty` points for each of `count` problems, stopping at 0.
+fn penalize(count: usize, penalty: u32) -> u32 {
+    let total = u32::try_from(count)
+        .unwrap_or(u32::MAX)
+        .saturating_mul(penalty);
+    100u32.saturating_sub(total)
+}
+
+fn length_score(len: usize) -> u32 {
+    match len {
+        0..4 => penalize(4 - len, 20),
+        4..=10 => 100,
+        _ => penalize(len - 10, 8),
+    }
+}
+
+fn typability_score(canonical: &str) -> u32 {
+    let finger = |c: char| FINGERS.iter().position(|keys| keys.contains(c));
+    let letters: Vec<char> = canonical.chars().filter(|&c| c != '_').collect();
+    let same_finger = letters
+        .windows(2)
+        .filter(|pair| {
+            pair[0] != pair[1] && finger(pair[0]).is_some_and(|f| finger(pair[1]) == Some(f))
+        })
+        .count();
+    let digits = letters.iter().filter(|c| c.is_ascii_digit()).count();
+    let rare = letters.iter().filter(|c| RARE_LETTERS.contains(c)).count();
+    penalize(same_finger * 2 + digits + rare, 10)
+}
+
+fn pronounceability_score(canonical: &str) -> u32 {
+    let is_vowel = |c: char| matches!(c, 'a' | 'e' | 'i' | 'o' | 'u' | 'y');
+    let mut problems = 0;
+    for word in canonical.split('_').filter(|w| !w.is_empty()) {
+        let letters: Vec<char> = word.chars().filter(char::is_ascii_alphabetic).collect();
+        if letters.len() >= 3 && !letters.iter().copied().any(is_vowel) {
+            problems += 2;
+        }
+        let runs = letters.chunk_by(|a, b| is_vowel(*a) == is_vowel(*b));
+        for run in runs {
+            let limit = if is_vowel(run[0]) { 2 } else { 3 };
+            problems += run.len().saturating_sub(limit);
+        }
+    }
+    penalize(problems, 25)
+}
+
+fn separator_score(name: &str) -> u32 {
+    let hyphens = name.matches('-').count();
+    let underscores = name.matches('_').count();
+    if hyphens > 0 && underscores > 0 {
+        return 40;
+    }
+    match hyphens + underscores {
+        0 => 100,
+        1 => 90,
+        2 => 75,
+        _ => 50,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn weights_add_up_to_100() {
+        let w = WEIGHTS;
+        assert_eq!(
+            w.length
+                + w.typability
+                + w.pronounceability
+                + w.dictionary
+                + w.similarity
+                + w.separators,
+            100
+        );
+    }
+
+    #[test]
+    fn comfortable_lengths_score_full() {
+        assert_eq!(length_score(4), 100);
+        assert_eq!(length_score(10), 100);
+        assert_eq!(length_score(2), 60);
+        assert_eq!(length_score(15), 60);
+        assert_eq!(length_score(64), 0);
+    }
+
+    #[test]
+    fn same_finger_jumps_digits_and_rare_letters_cost() {
+        assert_eq!(typability_score("tiger"), 100);
+        // `ed` is typed with the same finger; `ll` is the same key
+        assert_eq!(typability_score("fled"), 80);
+        assert_eq!(typability_score("silly"), 100);
+        assert_eq!(typability_score("jinx2"), 70);
+    }
+
+    #[test]
+    fn letter_runs_are_hard_to_say() {
+        assert_eq!(pronounceability_score("marble"), 100);
+        assert_eq!(pronounceability_score("strengths"), 50);
+        assert_eq!(pronounceability_score("queue"), 50);
+        // `http` has no vowel at all, on top of its long consonant run
+        assert_eq!(pronounceability_score("http_client"), 25);
+    }
+
+    #[test]
+    fn separators_cost_and_mixing_costs_more() {
+        assert_eq!(separator_score("foobar"), 100);
+        assert_eq!(separator_score("foo-bar"), 90);
+        assert_eq!(separator_score("foo_bar_baz"), 75);
+        assert_eq!(separator_score("foo-bar_baz"), 40);
+    }
+
+    #[test]
+    fn dictionary_words_and_lookalikes_score_low() {
+        assert_eq!(score("lantern").dictionary, 30);
+        assert_eq!(score("lan-tern").dictionary, 30);
+        assert_eq!(score("lanternix").dictionary, 100);
+        assert_eq!(score("serde-rs").similarity, 20);
+        assert_eq!(score("lanternix").similarity, 100);
+    }
+
+    #[test]
+    fn totals_are_weighted() {
+        let perfect = score("tovira");
+        assert_eq!(perfect.total, 100, "{perfect:?}");
+        let word = score("lantern");
+        assert_eq!(word.total, 90, "{word:?}");
+    }
+}
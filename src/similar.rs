@@ -0,0 +1,293 @@
+//! Similarity warnings against popular crates.
+//!
+//! crates.io flags new crates that look like typosquats of popular ones. An
+//! available name can still be a poor choice if it is one keystroke away from
+//! `serde` or differs from `tokio` only by a `-rs` suffix. This module compares
+//! a name's canonical form against a bundled snapshot of the most downloaded
+//! crates using edit distance, adjacent transpositions, separator and affix
+//! stripping, and a coarse phonetic key.
+
+use std::fmt;
+use std::sync::LazyLock;
+
+use crate::check::canon_crate_name;
+
+/// Canonical names of the most downloaded crates, most popular first.
+static POPULAR_CRATES: LazyLock<Vec<String>> = LazyLock::new(|| {
+    include_str!("data/popular-crates.txt")
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(canon_crate_name)
+        .collect()
+});
+
+/// Affixes that are commonly bolted onto an existing crate's name
+/// (canonical form, so hyphens appear as underscores).
+const PREFIXES: &[&str] = &["rust_", "rs_", "lib_"];
+const SUFFIXES: &[&str] = &["_rs", "_rust", "_lib", "_crate"];
+
+/// Names shorter than this are too short for edit-distance matching to mean
+/// anything (`brand`, `sand` and `randy` are all one edit from `rand`); only
+/// separator and affix matches are reported for them.
+const MIN_FUZZY_LENGTH: usize = 5;
+
+/// Names at least this long tolerate an edit distance of 2 instead of 1.
+const LONG_NAME_LENGTH: usize = 9;
+
+/// Why a name was considered similar to a popular crate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Resemblance {
+    /// Two adjacent characters are swapped (`sedre` vs `serde`).
+    Transposition,
+    /// A small number of insertions, deletions or substitutions.
+    EditDistance(usize),
+    /// Identical once separators are removed (`serdejson` vs `serde_json`).
+    Separators,
+    /// Identical once a common affix is stripped (`serde-rs` vs `serde`).
+    Affix,
+    /// Sounds the same (`tokyo` vs `tokio`).
+    Phonetic,
+}
+
+impl fmt::Display for Resemblance {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Transposition => write!(f, "transposition"),
+            Self::EditDistance(d) => write!(f, "edit distance {d}"),
+            Self::Separators => write!(f, "separators"),
+            Self::Affix => write!(f, "affix"),
+            Self::Phonetic => write!(f, "phonetic"),
+        }
+    }
+}
+
+/// A popular crate that a name closely resembles.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Similar {
+    /// Canonical name of the popular crate.
+    pub name: String,
+    /// How the names resemble each other.
+    pub resemblance: Resemblance,
+}
+
+impl fmt::Display for Similar {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "similar to `{}`", self.name)
+    }
+}
+
+/// Find the most popular crate that `name` closely resembles.
+///
+/// A name that is canonically identical to a popular crate is not reported:
+/// it is simply taken.
+///
+/// ```
+/// use cargo_avail::similar::{find_similar, Resemblance};
+///
+/// let similar = find_similar("sedre").unwrap();
+/// assert_eq!(similar.name, "serde");
+/// assert_eq!(similar.resemblance, Resemblance::Transposition);
+/// assert!(find_similar("zzzyyyxxxwww").is_none());
+/// ```
+#[must_use]
+pub fn find_similar(name: &str) -> Option<Similar> {
+    let canonical = canon_crate_name(name);
+    POPULAR_CRATES.iter().find_map(|popular| {
+        resemblance(&canonical, popular).map(|resemblance| Similar {
+            name: popular.clone(),
+            resemblance,
+        })
+    })
+}
+
+/// Compare two canonical names, most specific test first.
+fn resemblance(name: &str, popular: &str) -> Option<Resemblance> {
+    if name == popular {
+        return None;
+    }
+    if name.replace('_', "") == popular.replace('_', "") {
+        return Some(Resemblance::Separators);
+    }
+    if strip_affixes(name) == popular || strip_affixes(popular) == name {
+        return Some(Resemblance::Affix);
+    }
+    let len = name.len().min(popular.len());
+    if len < MIN_FUZZY_LENGTH {
+        return None;
+    }
+    // A different version number is a successor or sibling, not a typo
+    // (`bytes2`, `sha3`)
+    let unversioned = |n: &str| n.trim_end_matches(|c: char| c.is_ascii_digit()).to_string();
+    if unversioned(name) == unversioned(popular) {
+        return None;
+    }
+    if is_transposition(name, popular) {
+        return Some(Resemblance::Transposition);
+    }
+    let max_distance = if len >= LONG_NAME_LENGTH { 2 } else { 1 };
+    let distance = levenshtein(name, popular);
+    if distance <= max_distance {
+        return Some(Resemblance::EditDistance(distance));
+    }
+    let key = phonetic_key(name);
+    if key.len() >= 3 && key == phonetic_key(popular) {
+        return Some(Resemblance::Phonetic);
+    }
+    None
+}
+
+fn strip_affixes(name: &str) -> &str {
+    let name = PREFIXES
+        .iter()
+        .find_map(|p| name.strip_prefix(p))
+        .unwrap_or(name);
+    SUFFIXES
+        .iter()
+        .find_map(|s| name.strip_suffix(s))
+        .unwrap_or(name)
+}
+
+/// Whether `a` becomes `b` by swapping exactly one pair of adjacent characters.
+fn is_transposition(a: &str, b: &str) -> bool {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    if a.len() != b.len() {
+        return false;
+    }
+    let diffs: Vec<usize> = (0..a.len()).filter(|&i| a[i] != b[i]).collect();
+    matches!(diffs.as_slice(), &[i, j] if j == i + 1 && a[i] == b[j] && a[j] == b[i])
+}
+
+/// Levenshtein distance over bytes (canonical names are ASCII).
+fn levenshtein(a: &str, b: &str) -> usize {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut curr = vec![0; b.len() + 1];
+    for (i, &ca) in a.iter().enumerate() {
+        curr[0] = i + 1;
+        for (j, &cb) in b.iter().enumerate() {
+            let substitution = prev[j] + usize::from(ca != cb);
+            curr[j + 1] = substitution.min(prev[j + 1] + 1).min(curr[j] + 1);
+        }
+        std::mem::swap(&mut prev, &mut curr);
+    }
+    prev[b.len()]
+}
+
+/// A coarse phonetic key: separators removed, letters that sound alike merged,
+/// vowels after the first letter dropped, and repeated letters collapsed.
+fn phonetic_key(name: &str) -> String {
+    let spelled = name
+        .replace('_', "")
+        .replace("ph", "f")
+        .replace("ck", "k")
+        .replace('x', "ks");
+    let mut key = String::with_capacity(spelled.len());
+    for (i, ch) in spelled.chars().enumerate() {
+        let ch = match ch {
+            'c' | 'q' => 'k',
+            'z' => 's',
+            'y' => 'i',
+            other => other,
+        };
+        if i > 0 && matches!(ch, 'a' | 'e' | 'i' | 'o' | 'u') {
+            continue;
+        }
+        if key.ends_with(ch) {
+            continue;
+        }
+        key.push(ch);
+    }
+    key
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn popular_crates_are_loaded_in_order() {
+        assert_eq!(POPULAR_CRATES.first().map(String::as_str), Some("syn"));
+        assert!(POPULAR_CRATES.iter().any(|c| c == "serde_json"));
+        assert!(POPULAR_CRATES.iter().all(|c| !c.contains('-')));
+    }
+
+    #[test]
+    fn canonical_match_is_not_similar() {
+        assert_eq!(find_similar("serde-json"), None);
+        assert_eq!(find_similar("Tokio"), None);
+    }
+
+    #[test]
+    fn transposition_detected() {
+        let similar = find_similar("tokoi").unwrap();
+        assert_eq!(similar.name, "tokio");
+        assert_eq!(similar.resemblance, Resemblance::Transposition);
+    }
+
+    #[test]
+    fn single_edit_detected() {
+        let similar = find_similar("serdr").unwrap();
+        assert_eq!(similar.name, "serde");
+        assert_eq!(similar.resemblance, Resemblance::EditDistance(1));
+    }
+
+    #[test]
+    fn separator_stripping_detected() {
+        let similar = find_similar("serdejson").unwrap();
+        assert_eq!(similar.name, "serde_json");
+        assert_eq!(similar.resemblance, Resemblance::Separators);
+    }
+
+    #[test]
+    fn affix_stripping_detected() {
+        let similar = find_similar("rust-anyhow").unwrap();
+        assert_eq!(similar.name, "anyhow");
+        assert_eq!(similar.resemblance, Resemblance::Affix);
+        assert_eq!(find_similar("reqwest-rs").unwrap().name, "reqwest");
+    }
+
+    #[test]
+    fn respelled_long_name_is_one_edit_away() {
+        let similar = find_similar("kriterion").unwrap();
+        assert_eq!(similar.name, "criterion");
+        assert_eq!(similar.resemblance, Resemblance::EditDistance(1));
+    }
+
+    #[test]
+    fn phonetic_match_detected() {
+        let similar = find_similar("phastrand").unwrap();
+        assert_eq!(similar.name, "fastrand");
+        assert_eq!(similar.resemblance, Resemblance::Phonetic);
+    }
+
+    #[test]
+    fn short_names_need_an_exact_structural_match() {
+        // `sin` is one edit from `syn`, but three-letter names are too short
+        // for edit distance to be meaningful.
+        assert_eq!(find_similar("sin"), None);
+        for name in ["brand", "grand", "randy", "sand", "hand"] {
+            assert_eq!(find_similar(name), None, "{name}");
+        }
+    }
+
+    #[test]
+    fn version_suffixes_are_not_similar() {
+        assert_eq!(find_similar("bytes2"), None);
+        assert_eq!(find_similar("sha3"), None);
+    }
+
+    #[test]
+    fn unrelated_name_is_not_similar() {
+        assert_eq!(find_similar("zzzyyyxxxwww-not-a-real-crate"), None);
+    }
+
+    #[test]
+    fn levenshtein_basics() {
+        assert_eq!(levenshtein("kitten", "sitting"), 3);
+        assert_eq!(levenshtein("", "abc"), 3);
+        assert_eq!(levenshtein("same", "same"), 0);
+    }
+}
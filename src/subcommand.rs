@@ -0,0 +1,327 @@
+//! Command clashes for `cargo-*` crates.
+//!
+//! Installing a crate named `cargo-foo` adds a `cargo foo` command, but only
+//! if nothing else answers to `foo` first. Built-in commands and aliases from
+//! cargo configuration take precedence over installed plugins, so a
+//! `cargo-build` crate could never run as `cargo build`; and a command that a
+//! popular plugin already provides (`cargo watch`, `cargo nextest`) would
+//! fight it for the same binary name. [`subcommand_conflicts`] reports these
+//! clashes, which crates.io knows nothing about.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::path::Path;
+use std::sync::LazyLock;
+
+use crate::check::canon_crate_name;
+use crate::config::{ConfigError, cargo_home, read_toml};
+
+/// The prefix cargo looks for when running external subcommands.
+pub const PLUGIN_PREFIX: &str = "cargo-";
+
+/// Commands built into cargo, including its built-in aliases (`b`, `c`, `d`,
+/// `r`, `rm`, `t`).
+const BUILTIN_COMMANDS: &[&str] = &[
+    "add",
+    "b",
+    "bench",
+    "build",
+    "c",
+    "check",
+    "clean",
+    "config",
+    "d",
+    "doc",
+    "fetch",
+    "fix",
+    "generate-lockfile",
+    "git-checkout",
+    "help",
+    "info",
+    "init",
+    "install",
+    "locate-project",
+    "login",
+    "logout",
+    "metadata",
+    "new",
+    "owner",
+    "package",
+    "pkgid",
+    "publish",
+    "r",
+    "read-manifest",
+    "remove",
+    "report",
+    "rm",
+    "run",
+    "rustc",
+    "rustdoc",
+    "search",
+    "t",
+    "test",
+    "tree",
+    "uninstall",
+    "update",
+    "vendor",
+    "verify-project",
+    "version",
+    "yank",
+];
+
+/// The commands of popular plugins, and the crate providing each.
+static PLUGIN_COMMANDS: LazyLock<HashMap<String, String>> = LazyLock::new(|| {
+    let mut commands = HashMap::new();
+    let lines = include_str!("data/cargo-plugins.txt")
+        .lines()
+        .filter(|line| !line.trim().is_empty() && !line.starts_with('#'));
+    for line in lines {
+        let mut words = line.split_whitespace();
+        let Some(crate_name) = words.next() else {
+            continue;
+        };
+        let listed: Vec<&str> = words.collect();
+        let provided = match (listed.is_empty(), crate_name.strip_prefix(PLUGIN_PREFIX)) {
+            (false, _) => listed,
+            (true, Some(command)) => vec![command],
+            (true, None) => Vec::new(),
+        };
+        for command in provided {
+            commands
+                .entry(command.to_string())
+                .or_insert_with(|| crate_name.to_string());
+        }
+    }
+    commands
+});
+
+/// What a `cargo-*` crate's command clashes with.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ConflictKind {
+    /// A command built into cargo, which always wins.
+    Builtin,
+    /// An `[alias]` in cargo configuration, which wins over plugins.
+    Alias {
+        /// What the alias expands to.
+        expansion: String,
+    },
+    /// The command of a popular plugin crate.
+    Plugin {
+        /// The crate providing the command.
+        crate_name: String,
+    },
+}
+
+impl ConflictKind {
+    /// A short label for the kind of clash: `builtin`, `alias` or `plugin`.
+    #[must_use]
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Builtin => "builtin",
+            Self::Alias { .. } => "alias",
+            Self::Plugin { .. } => "plugin",
+        }
+    }
+}
+
+/// A clash between a `cargo-*` crate's command and an existing one.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct Conflict {
+    /// The command the crate would install: `foo` for `cargo-foo`.
+    pub command: String,
+    /// What it clashes with.
+    pub kind: ConflictKind,
+}
+
+impl fmt::Display for Conflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let command = &self.command;
+        match &self.kind {
+            ConflictKind::Builtin => write!(f, "`cargo {command}` is built in"),
+            ConflictKind::Alias { expansion } => {
+                write!(f, "`cargo {command}` is an alias for `{expansion}`")
+            }
+            ConflictKind::Plugin { crate_name } => {
+                write!(f, "`cargo {command}` is provided by `{crate_name}`")
+            }
+        }
+    }
+}
+
+/// The clashes of a `cargo-*` crate's command with built-in commands,
+/// `aliases` (as loaded by [`cargo_aliases`]) and popular plugins, most
+/// severe first. Empty for names without the `cargo-` prefix.
+///
+/// A plugin is not reported as clashing with itself.
+///
+/// ```
+/// use std::collections::HashMap;
+///
+/// use cargo_avail::subcommand::{ConflictKind, subcommand_conflicts};
+///
+/// let aliases = HashMap::new();
+/// let conflicts = subcommand_conflicts("cargo-build", &aliases);
+/// assert_eq!(conflicts[0].kind, ConflictKind::Builtin);
+/// assert!(subcommand_conflicts("cargo-frobnicate", &aliases).is_empty());
+/// assert!(subcommand_conflicts("build", &aliases).is_empty());
+/// ```
+#[must_use]
+pub fn subcommand_conflicts<S: std::hash::BuildHasher>(
+    name: &str,
+    aliases: &HashMap<String, String, S>,
+) -> Vec<Conflict> {
+    let Some(command) = name.strip_prefix(PLUGIN_PREFIX) else {
+        return Vec::new();
+    };
+    let conflict = |kind| Conflict {
+        command: command.to_string(),
+        kind,
+    };
+    let mut conflicts = Vec::new();
+    if BUILTIN_COMMANDS.contains(&command) {
+        conflicts.push(conflict(ConflictKind::Builtin));
+    }
+    if let Some(expansion) = aliases.get(command) {
+        conflicts.push(conflict(ConflictKind::Alias {
+            expansion: expansion.clone(),
+        }));
+    }
+    if let Some(crate_name) = PLUGIN_COMMANDS
+        .get(command)
+        .filter(|crate_name| canon_crate_name(crate_name) != canon_crate_name(name))
+    {
+        conflicts.push(conflict(ConflictKind::Plugin {
+            crate_name: crate_name.clone(),
+        }));
+    }
+    conflicts
+}
+
+/// The `[alias]` table cargo would use in `dir`: from `.cargo/config.toml`
+/// in `dir` and each of its ancestors, then `$CARGO_HOME/config.toml`, with
+/// the closest definition of each alias winning, as in cargo. Aliases
+/// written as lists are joined with spaces.
+///
+/// # Errors
+///
+/// Returns [`ConfigError`] if a configuration file exists but cannot be
+/// read or parsed.
+pub fn cargo_aliases(dir: &Path) -> Result<HashMap<String, String>, ConfigError> {
+    let config_dirs = dir
+        .ancestors()
+        .map(|ancestor| ancestor.join(".cargo"))
+        .chain(cargo_home());
+    let mut aliases = HashMap::new();
+    for config_dir in config_dirs {
+        if let Some(config) = read_toml(&config_dir, &["config.toml", "config"])? {
+            for (alias, expansion) in alias_table(&config) {
+                aliases.entry(alias).or_insert(expansion);
+            }
+        }
+    }
+    Ok(aliases)
+}
+
+/// The aliases in one configuration file.
+fn alias_table(config: &toml::Table) -> Vec<(String, String)> {
+    let Some(table) = config.get("alias").and_then(toml::Value::as_table) else {
+        return Vec::new();
+    };
+    table
+        .iter()
+        .filter_map(|(alias, value)| {
+            let expansion = match value {
+                toml::Value::String(s) => s.clone(),
+                toml::Value::Array(words) => words
+                    .iter()
+                    .map(toml::Value::as_str)
+                    .collect::<Option<Vec<_>>>()?
+                    .join(" "),
+                _ => return None,
+            };
+            Some((alias.clone(), expansion))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds(name: &str, aliases: &HashMap<String, String>) -> Vec<&'static str> {
+        subcommand_conflicts(name, aliases)
+            .iter()
+            .map(|c| c.kind.label())
+            .collect()
+    }
+
+    #[test]
+    fn builtins_and_their_aliases_clash() {
+        let none = HashMap::new();
+        assert_eq!(kinds("cargo-publish", &none), ["builtin"]);
+        assert_eq!(kinds("cargo-b", &none), ["builtin"]);
+        assert_eq!(kinds("cargo-publisher", &none), Vec::<&str>::new());
+    }
+
+    #[test]
+    fn plugins_clash_but_not_with_themselves() {
+        let none = HashMap::new();
+        assert_eq!(kinds("cargo-watch", &none), Vec::<&str>::new());
+        assert_eq!(
+            subcommand_conflicts("cargo-upgrade", &none),
+            [Conflict {
+                command: "upgrade".to_string(),
+                kind: ConflictKind::Plugin {
+                    crate_name: "cargo-edit".to_string()
+                },
+            }]
+        );
+        // `cargo fmt` comes from rustfmt, not from a `cargo-fmt` crate
+        assert_eq!(kinds("cargo-fmt", &none), ["plugin"]);
+    }
+
+    #[test]
+    fn user_aliases_clash() {
+        let aliases = HashMap::from([
+            ("xtask".to_string(), "run --package xtask --".to_string()),
+            ("check".to_string(), "clippy".to_string()),
+        ]);
+        let conflicts = subcommand_conflicts("cargo-xtask", &aliases);
+        assert_eq!(
+            conflicts[0].to_string(),
+            "`cargo xtask` is an alias for `run --package xtask --`"
+        );
+        assert_eq!(kinds("cargo-check", &aliases), ["builtin", "alias"]);
+    }
+
+    #[test]
+    fn only_cargo_prefixed_names_are_checked() {
+        let none = HashMap::new();
+        assert!(subcommand_conflicts("build", &none).is_empty());
+        assert!(subcommand_conflicts("cargo_build", &none).is_empty());
+    }
+
+    #[test]
+    fn alias_tables_accept_strings_and_lists() {
+        let config: toml::Table = r#"
+            [alias]
+            xtask = "run --package xtask --"
+            lint = ["clippy", "--all-targets"]
+            broken = 3
+        "#
+        .parse()
+        .unwrap();
+        let mut aliases = alias_table(&config);
+        aliases.sort();
+        assert_eq!(
+            aliases,
+            [
+                ("lint".to_string(), "clippy --all-targets".to_string()),
+                ("xtask".to_string(), "run --package xtask --".to_string()),
+            ]
+        );
+        assert!(alias_table(&toml::Table::new()).is_empty());
+    }
+}
@@ -0,0 +1,330 @@
+//! Name suggestions generated from keywords.
+//!
+//! Picking a name is the real job; checking it is only half of it.
+//! [`candidates`] turns a few keywords describing a crate (`parser fast json`)
+//! into candidate names: the keywords on their own, joined in pairs and all
+//! together, with affixes, truncated (`parjson`) and blended (`paron`). It
+//! works offline, dropping candidates that are invalid or reserved, and ranks
+//! the rest by their [quality score](crate::score) so the most promising names
+//! are checked first.
+
+use std::collections::HashSet;
+
+use serde::Serialize;
+
+use crate::check::validation::validate_crate_name;
+use crate::check::{canon_crate_name, is_reserved};
+use crate::score::{Score, score};
+use crate::variants::{Affix, variants};
+
+/// The separators keywords are joined with when none are configured.
+pub const DEFAULT_SEPARATORS: &[&str] = &["-", ""];
+
+/// How candidates are generated from keywords.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SuggestOptions {
+    /// Separators to join keywords with. `""` joins them directly; anything
+    /// other than `-`, `_` or `""` yields invalid names, which are dropped.
+    pub separators: Vec<String>,
+    /// Affixes tried on each keyword.
+    pub affixes: Vec<Affix>,
+    /// Whether to shorten the first keyword of a pair to its first syllable.
+    pub truncations: bool,
+    /// Whether to blend pairs of keywords into a single word.
+    pub blends: bool,
+}
+
+impl Default for SuggestOptions {
+    fn default() -> Self {
+        Self {
+            separators: DEFAULT_SEPARATORS.iter().map(ToString::to_string).collect(),
+            affixes: Affix::defaults(),
+            truncations: true,
+            blends: true,
+        }
+    }
+}
+
+/// How a candidate was built from the keywords.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+pub enum Origin {
+    /// A keyword on its own: `json`.
+    Keyword,
+    /// Keywords joined with a separator: `fast-json`.
+    Compound,
+    /// A keyword with an affix: `json-rs`.
+    Affixed,
+    /// A truncated keyword joined to another: `parjson`.
+    Truncated,
+    /// Two keywords blended into one word: `paron`.
+    Blend,
+}
+
+/// A generated name that passed the offline checks.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[non_exhaustive]
+pub struct Candidate {
+    /// The candidate name.
+    pub name: String,
+    /// How it was built.
+    pub origin: Origin,
+    /// Its quality score, as for any checked name.
+    pub score: Score,
+}
+
+impl Candidate {
+    fn new(name: String, origin: Origin) -> Self {
+        let score = score(&name);
+        Self {
+            name,
+            origin,
+            score,
+        }
+    }
+}
+
+/// Split keywords into lowercase alphanumeric words, dropping duplicates.
+fn words<S: AsRef<str>>(keywords: &[S]) -> Vec<String> {
+    let mut seen = HashSet::new();
+    keywords
+        .iter()
+        .flat_map(|k| {
+            k.as_ref()
+                .split(|c: char| !c.is_ascii_alphanumeric())
+                .map(str::to_ascii_lowercase)
+                .collect::<Vec<_>>()
+        })
+        .filter(|w| !w.is_empty() && seen.insert(w.clone()))
+        .collect()
+}
+
+fn is_vowel(c: u8) -> bool {
+    matches!(c, b'a' | b'e' | b'i' | b'o' | b'u')
+}
+
+/// The first syllable of a word: everything up to its first vowel run plus
+/// the consonant after it (`parser` -> `par`).
+fn first_syllable(word: &str) -> &str {
+    let bytes = word.as_bytes();
+    let Some(vowel) = bytes.iter().position(|&c| is_vowel(c)) else {
+        return word;
+    };
+    let after_vowels = bytes[vowel..]
+        .iter()
+        .position(|&c| !is_vowel(c))
+        .map_or(bytes.len(), |i| vowel + i);
+    &word[..(after_vowels + 1).min(bytes.len())]
+}
+
+/// A word shortened to its first syllable, if that leaves at least three
+/// characters and drops at least two.
+fn truncate(word: &str) -> Option<&str> {
+    let syllable = first_syllable(word);
+    (syllable.len() >= 3 && syllable.len() + 2 <= word.len()).then_some(syllable)
+}
+
+/// Blend two words into one: overlapping ends merge (`parser` + `serde` ->
+/// `parserde`), otherwise the first syllable of `a` takes over the rest of `b`
+/// from its first vowel (`parser` + `json` -> `paron`).
+fn blend(a: &str, b: &str) -> Option<String> {
+    let overlap = (2..a.len().min(b.len()))
+        .rev()
+        .find(|&k| a.ends_with(&b[..k]));
+    if let Some(k) = overlap {
+        return Some(format!("{a}{}", &b[k..]));
+    }
+    let head = truncate(a)?;
+    let vowel = b.bytes().position(is_vowel).filter(|&i| i > 0)?;
+    let blended = format!("{head}{}", &b[vowel..]);
+    (blended.len() >= 4).then_some(blended)
+}
+
+/// Generate candidate names from keywords, best first.
+///
+/// Keywords are split on anything other than ASCII letters and digits and
+/// lowercased. Candidates that fail crates.io validation or are reserved are
+/// dropped, as are canonical duplicates (keeping the best scored). Ties keep
+/// the shorter name, then generation order: keywords, compounds, affixed
+/// keywords, truncations and blends.
+///
+/// ```
+/// use cargo_avail::suggest::{SuggestOptions, candidates};
+///
+/// let names: Vec<String> = candidates(&["fast", "json"], &SuggestOptions::default())
+///     .into_iter()
+///     .map(|c| c.name)
+///     .collect();
+/// assert_eq!(names[..2], ["fast", "json"]);
+/// assert!(names.contains(&"fast-json".to_string()));
+/// ```
+#[must_use]
+pub fn candidates<S: AsRef<str>>(keywords: &[S], options: &SuggestOptions) -> Vec<Candidate> {
+    let words = words(keywords);
+    let pairs: Vec<(&str, &str)> = words
+        .iter()
+        .flat_map(|a| {
+            words
+                .iter()
+                .filter(move |b| *b != a)
+                .map(move |b| (a.as_str(), b.as_str()))
+        })
+        .collect();
+
+    let mut generated: Vec<Candidate> = Vec::new();
+    let mut push = |name: String, origin| generated.push(Candidate::new(name, origin));
+
+    for word in &words {
+        push(word.clone(), Origin::Keyword);
+    }
+    for sep in &options.separators {
+        for (a, b) in &pairs {
+            push(format!("{a}{sep}{b}"), Origin::Compound);
+        }
+        if words.len() > 2 {
+            push(words.join(sep), Origin::Compound);
+        }
+    }
+    for word in &words {
+        for variant in variants(word, &options.affixes) {
+            push(variant, Origin::Affixed);
+        }
+    }
+    if options.truncations {
+        for sep in &options.separators {
+            for (a, b) in &pairs {
+                if let Some(head) = truncate(a) {
+                    push(format!("{head}{sep}{b}"), Origin::Truncated);
+                }
+            }
+        }
+    }
+    if options.blends {
+        for (a, b) in &pairs {
+            if let Some(blended) = blend(a, b) {
+                push(blended, Origin::Blend);
+            }
+        }
+    }
+
+    generated.sort_by(|x, y| {
+        y.score
+            .total
+            .cmp(&x.score.total)
+            .then_with(|| x.name.len().cmp(&y.name.len()))
+    });
+    let mut seen = HashSet::new();
+    generated.retain(|c| {
+        validate_crate_name(&c.name).is_ok()
+            && !is_reserved(&c.name)
+            && seen.insert(canon_crate_name(&c.name))
+    });
+    generated
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(keywords: &[&str], options: &SuggestOptions) -> Vec<String> {
+        candidates(keywords, options)
+            .into_iter()
+            .map(|c| c.name)
+            .collect()
+    }
+
+    fn bare() -> SuggestOptions {
+        SuggestOptions {
+            separators: vec!["-".to_string()],
+            affixes: Vec::new(),
+            truncations: false,
+            blends: false,
+        }
+    }
+
+    #[test]
+    fn keywords_are_split_and_lowercased() {
+        assert_eq!(
+            words(&["Fast-JSON", "json", "parser!"]),
+            ["fast", "json", "parser"]
+        );
+    }
+
+    #[test]
+    fn truncation_keeps_the_first_syllable() {
+        assert_eq!(truncate("parser"), Some("par"));
+        assert_eq!(truncate("config"), Some("con"));
+        assert_eq!(truncate("serialize"), Some("ser"));
+        assert_eq!(truncate("json"), None);
+        assert_eq!(truncate("string"), None);
+        assert_eq!(truncate("http"), None);
+    }
+
+    #[test]
+    fn blends_merge_overlaps_or_syllables() {
+        assert_eq!(blend("parser", "serde").as_deref(), Some("parserde"));
+        assert_eq!(blend("parser", "json").as_deref(), Some("paron"));
+        // `json` has no syllable to cut, `async` no onset to replace
+        assert_eq!(blend("json", "fast"), None);
+        assert_eq!(blend("parser", "async"), None);
+    }
+
+    #[test]
+    fn compounds_follow_keywords() {
+        assert_eq!(
+            names(&["fast", "json"], &bare()),
+            ["fast", "json", "fast-json", "json-fast"]
+        );
+    }
+
+    #[test]
+    fn three_keywords_are_also_joined_together() {
+        let names = names(&["fast", "json", "parser"], &bare());
+        assert!(names.contains(&"fast-json-parser".to_string()));
+        assert!(!names.contains(&"json-fast-parser".to_string()));
+    }
+
+    #[test]
+    fn reserved_and_invalid_candidates_are_dropped() {
+        let names = names(&["std", "3d"], &bare());
+        assert!(!names.contains(&"std".to_string()));
+        assert!(!names.contains(&"3d".to_string()));
+        assert!(!names.contains(&"3d-std".to_string()));
+        assert_eq!(names, ["std-3d"]);
+    }
+
+    #[test]
+    fn canonical_duplicates_keep_the_best_score() {
+        let options = SuggestOptions {
+            separators: vec!["-".to_string(), "_".to_string()],
+            ..bare()
+        };
+        assert_eq!(
+            names(&["fast", "json"], &options),
+            ["fast", "json", "fast-json", "json-fast"]
+        );
+    }
+
+    #[test]
+    fn candidates_share_the_quality_score() {
+        let candidates = candidates(&["fast", "json"], &bare());
+        for candidate in &candidates {
+            assert_eq!(candidate.score, score(&candidate.name));
+        }
+    }
+
+    #[test]
+    fn all_generators_contribute_when_enabled() {
+        let candidates = candidates(&["parser", "json"], &SuggestOptions::default());
+        let origin = |name: &str| candidates.iter().find(|c| c.name == name).map(|c| c.origin);
+        assert_eq!(origin("json-rs"), Some(Origin::Affixed));
+        assert_eq!(origin("parjson"), Some(Origin::Truncated));
+        assert_eq!(origin("paron"), Some(Origin::Blend));
+        assert!(
+            candidates
+                .windows(2)
+                .all(|w| w[0].score.total >= w[1].score.total)
+        );
+    }
+}
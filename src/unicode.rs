@@ -0,0 +1,334 @@
+//! Diagnostics for non-ASCII characters that masquerade as valid crate names.
+//!
+//! Names copied from rendered documentation often pick up characters that look
+//! identical to ASCII but are rejected by crates.io: Cyrillic and Greek
+//! homoglyphs, typographic dashes, fullwidth forms, and invisible characters
+//! such as zero-width spaces or a byte order mark. The plain validation error
+//! only reports the first offending character, which is confusing when the
+//! input looks right. This module explains every such character and offers
+//! the ASCII transliteration.
+
+use std::fmt;
+
+/// Homoglyphs and invisible characters, with their Unicode names and the ASCII
+/// text they are mistaken for. An empty replacement means the character
+/// renders as nothing.
+const LOOKALIKES: &[(char, &str, &str)] = &[
+    // Dashes and hyphens
+    ('\u{2010}', "HYPHEN", "-"),
+    ('\u{2011}', "NON-BREAKING HYPHEN", "-"),
+    ('\u{2012}', "FIGURE DASH", "-"),
+    ('\u{2013}', "EN DASH", "-"),
+    ('\u{2014}', "EM DASH", "-"),
+    ('\u{2015}', "HORIZONTAL BAR", "-"),
+    ('\u{2212}', "MINUS SIGN", "-"),
+    ('\u{FE58}', "SMALL EM DASH", "-"),
+    ('\u{FE63}', "SMALL HYPHEN-MINUS", "-"),
+    ('\u{FF0D}', "FULLWIDTH HYPHEN-MINUS", "-"),
+    ('\u{FF3F}', "FULLWIDTH LOW LINE", "_"),
+    // Invisible characters
+    ('\u{00AD}', "SOFT HYPHEN", ""),
+    ('\u{180E}', "MONGOLIAN VOWEL SEPARATOR", ""),
+    ('\u{200B}', "ZERO WIDTH SPACE", ""),
+    ('\u{200C}', "ZERO WIDTH NON-JOINER", ""),
+    ('\u{200D}', "ZERO WIDTH JOINER", ""),
+    ('\u{2060}', "WORD JOINER", ""),
+    (
+        '\u{FEFF}',
+        "ZERO WIDTH NO-BREAK SPACE (byte order mark)",
+        "",
+    ),
+    // Cyrillic
+    ('\u{0430}', "CYRILLIC SMALL LETTER A", "a"),
+    ('\u{0435}', "CYRILLIC SMALL LETTER IE", "e"),
+    ('\u{043E}', "CYRILLIC SMALL LETTER O", "o"),
+    ('\u{0440}', "CYRILLIC SMALL LETTER ER", "p"),
+    ('\u{0441}', "CYRILLIC SMALL LETTER ES", "c"),
+    ('\u{0443}', "CYRILLIC SMALL LETTER U", "y"),
+    ('\u{0445}', "CYRILLIC SMALL LETTER HA", "x"),
+    ('\u{0455}', "CYRILLIC SMALL LETTER DZE", "s"),
+    (
+        '\u{0456}',
+        "CYRILLIC SMALL LETTER BYELORUSSIAN-UKRAINIAN I",
+        "i",
+    ),
+    ('\u{0458}', "CYRILLIC SMALL LETTER JE", "j"),
+    ('\u{04BB}', "CYRILLIC SMALL LETTER SHHA", "h"),
+    ('\u{04CF}', "CYRILLIC SMALL LETTER PALOCHKA", "l"),
+    ('\u{0501}', "CYRILLIC SMALL LETTER KOMI DE", "d"),
+    ('\u{051B}', "CYRILLIC SMALL LETTER QA", "q"),
+    ('\u{051D}', "CYRILLIC SMALL LETTER WE", "w"),
+    ('\u{0405}', "CYRILLIC CAPITAL LETTER DZE", "S"),
+    (
+        '\u{0406}',
+        "CYRILLIC CAPITAL LETTER BYELORUSSIAN-UKRAINIAN I",
+        "I",
+    ),
+    ('\u{0408}', "CYRILLIC CAPITAL LETTER JE", "J"),
+    ('\u{0410}', "CYRILLIC CAPITAL LETTER A", "A"),
+    ('\u{0412}', "CYRILLIC CAPITAL LETTER VE", "B"),
+    ('\u{0415}', "CYRILLIC CAPITAL LETTER IE", "E"),
+    ('\u{041A}', "CYRILLIC CAPITAL LETTER KA", "K"),
+    ('\u{041C}', "CYRILLIC CAPITAL LETTER EM", "M"),
+    ('\u{041D}', "CYRILLIC CAPITAL LETTER EN", "H"),
+    ('\u{041E}', "CYRILLIC CAPITAL LETTER O", "O"),
+    ('\u{0420}', "CYRILLIC CAPITAL LETTER ER", "P"),
+    ('\u{0421}', "CYRILLIC CAPITAL LETTER ES", "C"),
+    ('\u{0422}', "CYRILLIC CAPITAL LETTER TE", "T"),
+    ('\u{0425}', "CYRILLIC CAPITAL LETTER HA", "X"),
+    // Greek
+    ('\u{03B1}', "GREEK SMALL LETTER ALPHA", "a"),
+    ('\u{03B9}', "GREEK SMALL LETTER IOTA", "i"),
+    ('\u{03BA}', "GREEK SMALL LETTER KAPPA", "k"),
+    ('\u{03BD}', "GREEK SMALL LETTER NU", "v"),
+    ('\u{03BF}', "GREEK SMALL LETTER OMICRON", "o"),
+    ('\u{03C1}', "GREEK SMALL LETTER RHO", "p"),
+    ('\u{03C5}', "GREEK SMALL LETTER UPSILON", "u"),
+    ('\u{0391}', "GREEK CAPITAL LETTER ALPHA", "A"),
+    ('\u{0392}', "GREEK CAPITAL LETTER BETA", "B"),
+    ('\u{0395}', "GREEK CAPITAL LETTER EPSILON", "E"),
+    ('\u{0396}', "GREEK CAPITAL LETTER ZETA", "Z"),
+    ('\u{0397}', "GREEK CAPITAL LETTER ETA", "H"),
+    ('\u{0399}', "GREEK CAPITAL LETTER IOTA", "I"),
+    ('\u{039A}', "GREEK CAPITAL LETTER KAPPA", "K"),
+    ('\u{039C}', "GREEK CAPITAL LETTER MU", "M"),
+    ('\u{039D}', "GREEK CAPITAL LETTER NU", "N"),
+    ('\u{039F}', "GREEK CAPITAL LETTER OMICRON", "O"),
+    ('\u{03A1}', "GREEK CAPITAL LETTER RHO", "P"),
+    ('\u{03A4}', "GREEK CAPITAL LETTER TAU", "T"),
+    ('\u{03A5}', "GREEK CAPITAL LETTER UPSILON", "Y"),
+    ('\u{03A7}', "GREEK CAPITAL LETTER CHI", "X"),
+    // Latin lookalikes and compatibility ligatures (NFKC)
+    ('\u{0131}', "LATIN SMALL LETTER DOTLESS I", "i"),
+    ('\u{FB00}', "LATIN SMALL LIGATURE FF", "ff"),
+    ('\u{FB01}', "LATIN SMALL LIGATURE FI", "fi"),
+    ('\u{FB02}', "LATIN SMALL LIGATURE FL", "fl"),
+];
+
+const DIGIT_NAMES: [&str; 10] = [
+    "ZERO", "ONE", "TWO", "THREE", "FOUR", "FIVE", "SIX", "SEVEN", "EIGHT", "NINE",
+];
+
+/// A non-ASCII character that looks like ASCII text, or renders as nothing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Lookalike {
+    /// The offending character.
+    pub ch: char,
+    /// The character's Unicode name (e.g., `EN DASH`).
+    pub name: String,
+    /// The ASCII text the character is mistaken for; empty if it is invisible.
+    pub ascii: String,
+}
+
+impl Lookalike {
+    /// Look up a character in the homoglyph table.
+    ///
+    /// Returns `None` for ASCII characters and for non-ASCII characters with
+    /// no known ASCII lookalike.
+    ///
+    /// ```
+    /// use cargo_avail::unicode::Lookalike;
+    ///
+    /// let dash = Lookalike::of('\u{2013}').unwrap();
+    /// assert_eq!(dash.name, "EN DASH");
+    /// assert_eq!(dash.ascii, "-");
+    /// assert!(Lookalike::of('a').is_none());
+    /// ```
+    #[must_use]
+    pub fn of(ch: char) -> Option<Self> {
+        if ch.is_ascii() {
+            return None;
+        }
+        if let Some(&(_, name, ascii)) = LOOKALIKES.iter().find(|(c, _, _)| *c == ch) {
+            return Some(Self {
+                ch,
+                name: name.to_string(),
+                ascii: ascii.to_string(),
+            });
+        }
+        // Fullwidth ASCII forms (U+FF01..U+FF5E) are offset from ASCII by a constant.
+        let ascii = char::from_u32(u32::from(ch).checked_sub(0xFEE0)?)?;
+        let name = match ascii {
+            'A'..='Z' => format!("FULLWIDTH LATIN CAPITAL LETTER {ascii}"),
+            'a'..='z' => format!(
+                "FULLWIDTH LATIN SMALL LETTER {}",
+                ascii.to_ascii_uppercase()
+            ),
+            '0'..='9' => format!(
+                "FULLWIDTH DIGIT {}",
+                DIGIT_NAMES[ascii as usize - '0' as usize]
+            ),
+            _ => return None,
+        };
+        Some(Self {
+            ch,
+            name,
+            ascii: ascii.to_string(),
+        })
+    }
+
+    /// Whether the character renders as nothing (e.g., a zero-width space).
+    #[must_use]
+    pub fn is_invisible(&self) -> bool {
+        self.ascii.is_empty()
+    }
+}
+
+impl fmt::Display for Lookalike {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "U+{:04X} {}", u32::from(self.ch), self.name)?;
+        if self.is_invisible() {
+            write!(f, " is invisible")
+        } else {
+            write!(f, " looks like `{}`", self.ascii)
+        }
+    }
+}
+
+/// A crate name containing characters that look like ASCII but are not.
+///
+/// Returned by [`check_lookalikes`] and surfaced through
+/// [`CheckError::Lookalike`](crate::check::CheckError::Lookalike).
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct LookalikeName {
+    /// The name as given.
+    pub name: String,
+    /// Every lookalike character in the name, in order of appearance.
+    pub lookalikes: Vec<Lookalike>,
+    /// The ASCII transliteration, if it forms a valid crate name.
+    pub suggestion: Option<String>,
+}
+
+impl fmt::Display for LookalikeName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "crate name `{}` contains non-ASCII lookalike characters: ",
+            self.name
+        )?;
+        for (i, lookalike) in self.lookalikes.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{lookalike}")?;
+        }
+        if let Some(suggestion) = &self.suggestion {
+            write!(f, " (did you mean `{suggestion}`?)")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for LookalikeName {}
+
+/// Replace every known lookalike character with the ASCII text it resembles,
+/// and drop invisible characters. Other characters are left untouched.
+///
+/// ```
+/// use cargo_avail::unicode::transliterate;
+///
+/// assert_eq!(transliterate("\u{FEFF}foo\u{2013}b\u{0430}r"), "foo-bar");
+/// ```
+#[must_use]
+pub fn transliterate(name: &str) -> String {
+    let mut out = String::with_capacity(name.len());
+    for ch in name.chars() {
+        match Lookalike::of(ch) {
+            Some(lookalike) => out.push_str(&lookalike.ascii),
+            None => out.push(ch),
+        }
+    }
+    out
+}
+
+/// Check a name for homoglyphs and invisible characters.
+///
+/// # Errors
+///
+/// Returns a [`LookalikeName`] listing every offending character if any are
+/// found. Its suggestion is the [`transliterate`]d name when that passes
+/// crates.io validation.
+///
+/// ```
+/// use cargo_avail::unicode::check_lookalikes;
+///
+/// assert!(check_lookalikes("serde").is_ok());
+/// let err = check_lookalikes("\u{0455}erde").unwrap_err();
+/// assert_eq!(err.suggestion.as_deref(), Some("serde"));
+/// ```
+pub fn check_lookalikes(name: &str) -> Result<(), LookalikeName> {
+    let lookalikes: Vec<Lookalike> = name.chars().filter_map(Lookalike::of).collect();
+    if lookalikes.is_empty() {
+        return Ok(());
+    }
+    let transliterated = transliterate(name);
+    let suggestion = crate::check::validation::validate_crate_name(&transliterated)
+        .is_ok()
+        .then_some(transliterated);
+    Err(LookalikeName {
+        name: name.to_string(),
+        lookalikes,
+        suggestion,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn en_dash_explained() {
+        let dash = Lookalike::of('\u{2013}').unwrap();
+        assert_eq!(dash.to_string(), "U+2013 EN DASH looks like `-`");
+    }
+
+    #[test]
+    fn zero_width_space_is_invisible() {
+        let zwsp = Lookalike::of('\u{200B}').unwrap();
+        assert!(zwsp.is_invisible());
+        assert_eq!(zwsp.to_string(), "U+200B ZERO WIDTH SPACE is invisible");
+    }
+
+    #[test]
+    fn fullwidth_forms_map_to_ascii() {
+        assert_eq!(Lookalike::of('\u{FF41}').unwrap().ascii, "a");
+        assert_eq!(
+            Lookalike::of('\u{FF21}').unwrap().name,
+            "FULLWIDTH LATIN CAPITAL LETTER A"
+        );
+        assert_eq!(
+            Lookalike::of('\u{FF13}').unwrap().name,
+            "FULLWIDTH DIGIT THREE"
+        );
+        // Fullwidth punctuation other than `-` and `_` is not a lookalike
+        assert!(Lookalike::of('\u{FF0B}').is_none());
+    }
+
+    #[test]
+    fn unknown_non_ascii_is_not_a_lookalike() {
+        assert!(Lookalike::of('\u{4E2D}').is_none());
+    }
+
+    #[test]
+    fn bom_prefixed_name_suggests_stripped_name() {
+        let err = check_lookalikes("\u{FEFF}tokio").unwrap_err();
+        assert_eq!(err.lookalikes.len(), 1);
+        assert_eq!(err.suggestion.as_deref(), Some("tokio"));
+    }
+
+    #[test]
+    fn mixed_script_name_lists_every_lookalike() {
+        let err = check_lookalikes("s\u{0435}rd\u{0435}\u{2013}json").unwrap_err();
+        assert_eq!(err.lookalikes.len(), 3);
+        assert_eq!(err.suggestion.as_deref(), Some("serde-json"));
+        assert!(err.to_string().contains("CYRILLIC SMALL LETTER IE"));
+    }
+
+    #[test]
+    fn no_suggestion_when_transliteration_is_still_invalid() {
+        let err = check_lookalikes("f\u{043E}\u{043E}+bar").unwrap_err();
+        assert_eq!(err.suggestion, None);
+    }
+}
@@ -0,0 +1,228 @@
+//! Common affix variants of a crate name.
+//!
+//! When `foo` is taken, the usual next tries are `foo-rs`, `rust-foo`,
+//! `foo-cli` and so on. [`variants`] expands a name into those spellings,
+//! following the separator style the name already uses and skipping any that
+//! would collide canonically with each other or be invalid.
+
+use std::collections::HashSet;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::check::canon_crate_name;
+use crate::check::validation::validate_crate_name;
+
+/// The affixes tried when none are configured.
+pub const DEFAULT_AFFIXES: &[&str] = &["-rs", "-rust", "rust-", "-lib", "-cli", "-core"];
+
+/// The members checked alongside a family's base name when none are configured.
+pub const DEFAULT_FAMILY_MEMBERS: &[&str] = &["core", "macros", "derive", "cli"];
+
+/// A word attached before or after a name.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Affix {
+    /// Attached before the name, written `rust-`.
+    Prefix(String),
+    /// Attached after the name, written `-rs`.
+    Suffix(String),
+}
+
+impl Affix {
+    /// The affixes in [`DEFAULT_AFFIXES`].
+    #[must_use]
+    pub fn defaults() -> Vec<Self> {
+        DEFAULT_AFFIXES
+            .iter()
+            .filter_map(|a| a.parse().ok())
+            .collect()
+    }
+
+    /// Attach the affix to `name` using the separator `sep`.
+    fn apply(&self, name: &str, sep: char) -> String {
+        match self {
+            Self::Prefix(word) => format!("{word}{sep}{name}"),
+            Self::Suffix(word) => format!("{name}{sep}{word}"),
+        }
+    }
+
+    /// Whether `name` already carries this affix.
+    fn is_on(&self, name: &str) -> bool {
+        let canonical = canon_crate_name(name);
+        let word = canon_crate_name(match self {
+            Self::Prefix(word) | Self::Suffix(word) => word,
+        });
+        match self {
+            Self::Prefix(_) => canonical.starts_with(&format!("{word}_")),
+            Self::Suffix(_) => canonical.ends_with(&format!("_{word}")),
+        }
+    }
+}
+
+impl fmt::Display for Affix {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Prefix(word) => write!(f, "{word}-"),
+            Self::Suffix(word) => write!(f, "-{word}"),
+        }
+    }
+}
+
+impl FromStr for Affix {
+    type Err = String;
+
+    /// Parse `-word` or `_word` as a suffix and `word-` or `word_` as a prefix.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let is_sep = |c: char| c == '-' || c == '_';
+        let word = s.trim_matches(is_sep);
+        let valid_word =
+            !word.is_empty() && word.chars().all(|c| c.is_ascii_alphanumeric() || is_sep(c));
+        match (s.starts_with(is_sep), s.ends_with(is_sep)) {
+            (true, false) if valid_word => Ok(Self::Suffix(word.to_string())),
+            (false, true) if valid_word => Ok(Self::Prefix(word.to_string())),
+            _ => Err(format!(
+                "invalid affix `{s}` (expected a suffix like `-rs` or a prefix like `rust-`)"
+            )),
+        }
+    }
+}
+
+/// Expand a name into its affix variants, in affix order.
+///
+/// Variants use `_` if the name does and `-` otherwise. Affixes the name
+/// already carries are skipped, as are variants that are canonically equal to
+/// the name or an earlier variant, or that fail crates.io validation.
+///
+/// ```
+/// use cargo_avail::variants::{variants, Affix};
+///
+/// let affixes: Vec<Affix> = ["-rs", "rust-"].iter().map(|a| a.parse().unwrap()).collect();
+/// assert_eq!(variants("foo", &affixes), ["foo-rs", "rust-foo"]);
+/// assert_eq!(variants("foo_bar", &affixes), ["foo_bar_rs", "rust_foo_bar"]);
+/// assert_eq!(variants("foo-rs", &affixes), ["rust-foo-rs"]);
+/// ```
+#[must_use]
+pub fn variants(name: &str, affixes: &[Affix]) -> Vec<String> {
+    let sep = separator(name);
+    let mut seen = HashSet::from([canon_crate_name(name)]);
+    affixes
+        .iter()
+        .filter(|affix| !affix.is_on(name))
+        .map(|affix| affix.apply(name, sep))
+        .filter(|variant| validate_crate_name(variant).is_ok())
+        .filter(|variant| seen.insert(canon_crate_name(variant)))
+        .collect()
+}
+
+/// The names a multi-crate project needs: the base name followed by
+/// `base-member` for each member.
+///
+/// Members may be given with or without a leading separator (`core` or
+/// `-core`) and use the base name's separator style. Canonical duplicates are
+/// dropped.
+///
+/// ```
+/// use cargo_avail::variants::family;
+///
+/// assert_eq!(family("foo", &["core", "derive"]), ["foo", "foo-core", "foo-derive"]);
+/// assert_eq!(family("foo_bar", &["-cli"]), ["foo_bar", "foo_bar_cli"]);
+/// ```
+#[must_use]
+pub fn family<S: AsRef<str>>(base: &str, members: &[S]) -> Vec<String> {
+    let suffixes: Vec<Affix> = members
+        .iter()
+        .map(|m| m.as_ref().trim_start_matches(['-', '_']))
+        .filter(|m| !m.is_empty())
+        .map(|m| Affix::Suffix(m.to_string()))
+        .collect();
+    let sep = separator(base);
+    let mut seen = HashSet::new();
+    std::iter::once(base.to_string())
+        .chain(suffixes.iter().map(|suffix| suffix.apply(base, sep)))
+        .filter(|name| seen.insert(canon_crate_name(name)))
+        .collect()
+}
+
+/// The separator style a name already uses, defaulting to hyphens.
+pub(crate) fn separator(name: &str) -> char {
+    if name.contains('_') && !name.contains('-') {
+        '_'
+    } else {
+        '-'
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn affixes(list: &[&str]) -> Vec<Affix> {
+        list.iter().map(|a| a.parse().unwrap()).collect()
+    }
+
+    #[test]
+    fn affixes_parse_by_separator_position() {
+        assert_eq!("-rs".parse(), Ok(Affix::Suffix("rs".to_string())));
+        assert_eq!("_cli".parse(), Ok(Affix::Suffix("cli".to_string())));
+        assert_eq!("rust-".parse(), Ok(Affix::Prefix("rust".to_string())));
+        assert!("rs".parse::<Affix>().is_err());
+        assert!("-rs-".parse::<Affix>().is_err());
+        assert!("-".parse::<Affix>().is_err());
+        assert!("-r.s".parse::<Affix>().is_err());
+    }
+
+    #[test]
+    fn affixes_display_round_trip() {
+        assert_eq!(Affix::defaults().len(), DEFAULT_AFFIXES.len());
+        for affix in Affix::defaults() {
+            assert_eq!(affix.to_string().parse(), Ok(affix));
+        }
+    }
+
+    #[test]
+    fn default_variants_in_order() {
+        assert_eq!(
+            variants("foo", &Affix::defaults()),
+            [
+                "foo-rs", "foo-rust", "rust-foo", "foo-lib", "foo-cli", "foo-core"
+            ]
+        );
+    }
+
+    #[test]
+    fn existing_affixes_are_not_doubled() {
+        assert_eq!(
+            variants("foo_core", &affixes(&["-core", "-cli"])),
+            ["foo_core_cli"]
+        );
+        assert_eq!(
+            variants("rust-foo", &affixes(&["rust-"])),
+            Vec::<String>::new()
+        );
+    }
+
+    #[test]
+    fn canonical_duplicates_are_dropped() {
+        assert_eq!(variants("foo", &affixes(&["-rs", "_rs"])), ["foo-rs"]);
+    }
+
+    #[test]
+    fn family_members_follow_the_base_name() {
+        assert_eq!(
+            family("foo", DEFAULT_FAMILY_MEMBERS),
+            ["foo", "foo-core", "foo-macros", "foo-derive", "foo-cli"]
+        );
+        // Unlike variants, invalid members are kept so they block the family
+        assert_eq!(family("foo", &["a.b"]), ["foo", "foo-a.b"]);
+        assert_eq!(
+            family("foo", &["core", "-core", "_core"]),
+            ["foo", "foo-core"]
+        );
+    }
+
+    #[test]
+    fn invalid_variants_are_dropped() {
+        // `3d-rs` still starts with a digit; `lib-3d` does not
+        assert_eq!(variants("3d", &affixes(&["-rs", "lib-"])), ["lib-3d"]);
+    }
+}
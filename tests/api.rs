@@ -1,4 +1,8 @@
-use cargo_avail::check::{Availability, CheckError, Client, canon_crate_name, check_name};
+use cargo_avail::check::{
+    Availability, CheckError, Client, CollisionKind, canon_crate_name, check_name,
+    check_name_report, crate_owners, reverse_dependency_count,
+};
+use cargo_avail::namespace::related_crates;
 
 #[test]
 fn public_api_canon_crate_name() {
@@ -35,6 +39,14 @@ fn public_api_invalid_returns_error() {
     }
 }
 
+#[test]
+fn public_api_report_reserved_has_no_collision() {
+    let client = Client::new();
+    let report = check_name_report(&client, "std").unwrap();
+    assert_eq!(report.availability, Availability::Reserved);
+    assert!(report.collision.is_none());
+}
+
 #[test]
 fn client_default_equals_new() {
     let _client: Client = Client::default();
@@ -59,3 +71,72 @@ fn public_api_available_returns_available() {
         other => panic!("expected Available, got {other:?}"),
     }
 }
+
+#[test]
+#[ignore = "requires network access"]
+fn public_api_report_names_exact_collision() {
+    let client = Client::new();
+    let report = check_name_report(&client, "serde").unwrap();
+    assert_eq!(report.availability, Availability::Taken);
+    let collision = report
+        .collision
+        .expect("taken name should report collision");
+    assert_eq!(collision.name, "serde");
+    assert_eq!(collision.kind, CollisionKind::Exact);
+}
+
+#[test]
+#[ignore = "requires network access"]
+fn public_api_report_includes_details_for_taken() {
+    let client = Client::new();
+    let report = check_name_report(&client, "serde").unwrap();
+    let details = report.details.expect("taken name should have details");
+    assert!(details.downloads > 0);
+    assert!(details.versions > 0);
+    assert!(details.created_at.starts_with("2014-"));
+}
+
+#[test]
+#[ignore = "requires network access"]
+fn public_api_report_counts_yanked_versions() {
+    let client = Client::new();
+    let report = check_name_report(&client, "serde").unwrap();
+    assert!(!report.is_yanked_only());
+    let details = report.details.expect("taken name should have details");
+    assert!(details.yanked_versions < details.versions);
+    assert!(!details.all_yanked());
+}
+
+#[test]
+#[ignore = "requires network access"]
+fn public_api_crate_owners_lists_owners() {
+    let client = Client::new();
+    let owners = crate_owners(&client, "serde-json")
+        .unwrap()
+        .expect("serde_json should exist");
+    assert!(owners.iter().any(|o| o.login == "dtolnay"));
+    assert!(
+        crate_owners(&client, "zzzyyyxxxwww-not-a-real-crate")
+            .unwrap()
+            .is_none()
+    );
+}
+
+#[test]
+#[ignore = "requires network access"]
+fn public_api_reverse_dependency_count() {
+    let client = Client::new();
+    let count = reverse_dependency_count(&client, "serde")
+        .unwrap()
+        .expect("serde should exist");
+    assert!(count > 1000, "serde should have many dependents: {count}");
+}
+
+#[test]
+#[ignore = "requires network access"]
+fn public_api_related_crates_for_crowded_stem() {
+    let namespace = related_crates(&Client::new(), "my-tokio").unwrap();
+    assert_eq!(namespace.stem, "tokio");
+    assert!(namespace.is_crowded());
+    assert!(namespace.top.iter().any(|c| c.name == "tokio"));
+}
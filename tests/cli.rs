@@ -269,3 +269,1017 @@ fn hyphen_valued_names_via_separator() {
         "should report invalid name: stdout={stdout}"
     );
 }
+
+#[test]
+fn lookalike_characters_are_explained() {
+    let output = cargo_avail()
+        .args(["--json", "s\u{0442}d\u{2013}x"])
+        .output()
+        .expect("failed to execute");
+    assert_eq!(output.status.code(), Some(1));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let parsed: serde_json::Value =
+        serde_json::from_str(stdout.trim()).expect("should be valid JSON");
+    assert_eq!(parsed["status"], "invalid");
+    let error = parsed["error"].as_str().expect("should have error field");
+    assert!(
+        error.contains("U+2013 EN DASH looks like `-`"),
+        "error: {error}"
+    );
+}
+
+#[test]
+fn fix_flag_checks_transliterated_name() {
+    let output = cargo_avail()
+        .args(["--json", "--fix", "\u{FEFF}\u{0455}td"])
+        .output()
+        .expect("failed to execute");
+    assert_eq!(output.status.code(), Some(1));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let parsed: serde_json::Value =
+        serde_json::from_str(stdout.trim()).expect("should be valid JSON");
+    assert_eq!(parsed["name"], "std");
+    assert_eq!(parsed["status"], "reserved");
+    assert_eq!(parsed["fixed_from"], "\u{FEFF}\u{0455}td");
+}
+
+#[test]
+fn suggest_fix_checks_repaired_name() {
+    let output = cargo_avail()
+        .args(["--json", "--suggest-fix", "std."])
+        .output()
+        .expect("failed to execute");
+    assert_eq!(output.status.code(), Some(1));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let parsed: serde_json::Value =
+        serde_json::from_str(stdout.trim()).expect("should be valid JSON");
+    assert_eq!(parsed["name"], "std.");
+    assert_eq!(parsed["status"], "invalid");
+    assert_eq!(parsed["fix"]["name"], "std");
+    assert_eq!(parsed["fix"]["status"], "reserved");
+}
+
+#[test]
+fn suggest_fix_text_output_shows_both() {
+    let output = cargo_avail()
+        .args(["--suggest-fix", "core+"])
+        .output()
+        .expect("failed to execute");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("\tfix: core reserved"),
+        "should show the repaired name: {stdout}"
+    );
+}
+
+#[test]
+fn lint_flag_reports_findings_in_json() {
+    let output = cargo_avail()
+        .args(["--json", "--lint", "Core"])
+        .output()
+        .expect("failed to execute");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let parsed: serde_json::Value =
+        serde_json::from_str(stdout.trim()).expect("should be valid JSON");
+    assert_eq!(parsed["status"], "reserved");
+    assert_eq!(parsed["lints"][0]["lint"], "uppercase");
+    assert_eq!(parsed["lints"][0]["level"], "warn");
+}
+
+#[test]
+fn lint_levels_are_configurable() {
+    let output = cargo_avail()
+        .args(["-D", "uppercase", "-A", "shadows-std", "Core", "SYNC"])
+        .output()
+        .expect("failed to execute");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("reserved (deny: uppercase)"),
+        "stdout: {stdout}"
+    );
+    assert!(!stdout.contains("shadows-std"), "stdout: {stdout}");
+}
+
+#[test]
+fn lints_are_off_by_default() {
+    let output = cargo_avail()
+        .args(["--json", "Core"])
+        .output()
+        .expect("failed to execute");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(!stdout.contains("lints"), "stdout: {stdout}");
+}
+
+#[test]
+fn unknown_lint_is_a_usage_error() {
+    let output = cargo_avail()
+        .args(["--deny", "no-such-lint", "std"])
+        .output()
+        .expect("failed to execute");
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("unknown lint"), "stderr: {stderr}");
+}
+
+#[test]
+fn dormant_years_requires_classify_taken() {
+    let output = cargo_avail()
+        .args(["--dormant-years", "5", "std"])
+        .output()
+        .expect("failed to execute");
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn classify_taken_skips_untaken_names() {
+    let output = cargo_avail()
+        .args(["--json", "--classify-taken", "std"])
+        .output()
+        .expect("failed to execute");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let parsed: serde_json::Value =
+        serde_json::from_str(stdout.trim()).expect("should be valid JSON");
+    assert_eq!(parsed["status"], "reserved");
+    assert!(parsed.get("classification").is_none(), "stdout: {stdout}");
+}
+
+#[test]
+fn owned_without_token_is_a_usage_error() {
+    let empty_home = std::env::temp_dir().join("cargo-avail-test-empty-cargo-home");
+    std::fs::create_dir_all(&empty_home).expect("failed to create temp dir");
+    let output = cargo_avail()
+        .env("CARGO_HOME", &empty_home)
+        .env_remove("CARGO_REGISTRY_TOKEN")
+        .args(["--owned", "std"])
+        .output()
+        .expect("failed to execute");
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("no crates.io token"), "stderr: {stderr}");
+}
+
+#[test]
+fn owned_reads_token_from_credentials_file() {
+    let home = std::env::temp_dir().join("cargo-avail-test-credentials-home");
+    std::fs::create_dir_all(&home).expect("failed to create temp dir");
+    std::fs::write(
+        home.join("credentials.toml"),
+        "[registry]\ntoken = \"cio_test\"\n",
+    )
+    .expect("failed to write credentials");
+    let output = cargo_avail()
+        .env("CARGO_HOME", &home)
+        .env_remove("CARGO_REGISTRY_TOKEN")
+        .args(["--owned", "std"])
+        .output()
+        .expect("failed to execute");
+    // Reserved names never reach the ownership lookup
+    assert_eq!(output.status.code(), Some(1));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("reserved"), "stdout: {stdout}");
+}
+
+#[test]
+fn owner_logins_need_no_token() {
+    let empty_home = std::env::temp_dir().join("cargo-avail-test-empty-cargo-home");
+    std::fs::create_dir_all(&empty_home).expect("failed to create temp dir");
+    let output = cargo_avail()
+        .env("CARGO_HOME", &empty_home)
+        .env_remove("CARGO_REGISTRY_TOKEN")
+        .args([
+            "--owned",
+            "--owner",
+            "alice",
+            "--owner-team",
+            "github:acme:owners",
+            "std",
+        ])
+        .output()
+        .expect("failed to execute");
+    // Reserved names never reach the ownership lookup
+    assert_eq!(output.status.code(), Some(1));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("reserved"), "stdout: {stdout}");
+}
+
+#[test]
+fn subcommand_names_checked_after_separator() {
+    // `owners` is a subcommand, but after `--` it is a crate name to check
+    let output = cargo_avail()
+        .args(["--", "owners", "std"])
+        .output()
+        .expect("failed to execute");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<&str> = stdout.trim().lines().collect();
+    assert_eq!(lines.len(), 2, "should check both names: {stdout}");
+    assert!(lines[0].starts_with("owners\t"), "stdout: {stdout}");
+}
+
+#[test]
+fn owners_reports_invalid_names() {
+    let output = cargo_avail()
+        .args(["owners", "--json", "foo+bar"])
+        .output()
+        .expect("failed to execute");
+    assert_eq!(output.status.code(), Some(1));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let parsed: serde_json::Value =
+        serde_json::from_str(stdout.trim()).expect("should be valid JSON");
+    assert_eq!(parsed["name"], "foo+bar");
+    assert_eq!(parsed["status"], "invalid");
+    assert!(parsed.get("owners").is_none(), "stdout: {stdout}");
+}
+
+#[test]
+fn owners_without_names_exits_with_code_2() {
+    let output = cargo_avail()
+        .arg("owners")
+        .output()
+        .expect("failed to execute");
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn owners_rejects_malformed_expected_file() {
+    let path = std::env::temp_dir().join("cargo-avail-test-bad-owners.toml");
+    std::fs::write(&path, "requried = [\"github:acme:owners\"]\n").expect("failed to write");
+    let output = cargo_avail()
+        .args(["owners", "--expected"])
+        .arg(&path)
+        .arg("serde")
+        .output()
+        .expect("failed to execute");
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("unknown field"), "stderr: {stderr}");
+}
+
+#[test]
+fn claim_request_refuses_untaken_names() {
+    let output = cargo_avail()
+        .args(["claim-request", "std"])
+        .output()
+        .expect("failed to execute");
+    assert_eq!(output.status.code(), Some(1));
+    assert!(output.stdout.is_empty(), "no draft should be printed");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("reserved"), "stderr: {stderr}");
+}
+
+#[test]
+fn claim_request_accepts_check_repository() {
+    let output = cargo_avail()
+        .args(["claim-request", "--check-repository", "std"])
+        .output()
+        .expect("failed to execute");
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("reserved"), "stderr: {stderr}");
+}
+
+#[test]
+fn claim_request_rejects_unknown_format() {
+    let output = cargo_avail()
+        .args(["claim-request", "--format", "html", "serde"])
+        .output()
+        .expect("failed to execute");
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn rdeps_skips_untaken_names() {
+    let output = cargo_avail()
+        .args(["--json", "--entrenched", "100", "std"])
+        .output()
+        .expect("failed to execute");
+    assert_eq!(output.status.code(), Some(1));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let parsed: serde_json::Value =
+        serde_json::from_str(stdout.trim()).expect("should be valid JSON");
+    assert_eq!(parsed["status"], "reserved");
+    assert!(parsed.get("rdeps").is_none(), "stdout: {stdout}");
+    assert!(parsed.get("entrenched").is_none(), "stdout: {stdout}");
+}
+
+#[test]
+fn crowded_skips_unavailable_names() {
+    let output = cargo_avail()
+        .args(["--json", "--crowded", "std"])
+        .output()
+        .expect("failed to execute");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let parsed: serde_json::Value =
+        serde_json::from_str(stdout.trim()).expect("should be valid JSON");
+    assert_eq!(parsed["status"], "reserved");
+    assert!(parsed.get("related").is_none(), "stdout: {stdout}");
+}
+
+#[test]
+fn variants_are_grouped_under_their_name() {
+    let output = cargo_avail()
+        .args([
+            "--json",
+            "--affix",
+            "-rs",
+            "--affix",
+            "compiler-",
+            "builtins",
+        ])
+        .output()
+        .expect("failed to execute");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<&str> = stdout.trim().lines().collect();
+    assert_eq!(lines.len(), 1, "variants belong to one object: {stdout}");
+    let parsed: serde_json::Value = serde_json::from_str(lines[0]).expect("should be valid JSON");
+    assert_eq!(parsed["status"], "reserved");
+    let variants = parsed["variants"].as_array().expect("variants array");
+    let names: Vec<&str> = variants.iter().filter_map(|v| v["name"].as_str()).collect();
+    assert_eq!(names, ["builtins-rs", "compiler-builtins"]);
+    assert_eq!(variants[1]["status"], "reserved");
+}
+
+#[test]
+fn variants_skip_names_given_as_input() {
+    let output = cargo_avail()
+        .args([
+            "--json",
+            "--affix",
+            "compiler-",
+            "builtins",
+            "compiler_builtins",
+        ])
+        .output()
+        .expect("failed to execute");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(!stdout.contains("\"variants\""), "stdout: {stdout}");
+    assert_eq!(stdout.trim().lines().count(), 2, "stdout: {stdout}");
+}
+
+#[test]
+fn malformed_affix_is_a_usage_error() {
+    let output = cargo_avail()
+        .args(["--affix", "rs", "std"])
+        .output()
+        .expect("failed to execute");
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn family_is_blocked_by_any_member() {
+    let output = cargo_avail()
+        .args(["family", "--json", "std", "--members", "a.b"])
+        .output()
+        .expect("failed to execute");
+    assert_eq!(output.status.code(), Some(1));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let parsed: serde_json::Value =
+        serde_json::from_str(stdout.trim()).expect("should be a single JSON object");
+    assert_eq!(parsed["family"], "std");
+    assert_eq!(parsed["claimable"], false);
+    assert_eq!(parsed["members"][0]["status"], "reserved");
+    assert_eq!(parsed["members"][1]["status"], "invalid");
+    assert_eq!(parsed["blocked"], serde_json::json!(["std", "std-a.b"]));
+}
+
+#[test]
+fn family_text_output_summarizes_blockers() {
+    let output = cargo_avail()
+        .args(["family", "std", "--members", "a.b"])
+        .output()
+        .expect("failed to execute");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(stdout.lines().count(), 2, "stdout: {stdout}");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("family std: blocked by std, std-a.b"),
+        "stderr: {stderr}"
+    );
+}
+
+#[test]
+fn family_accepts_owner_flags() {
+    let output = cargo_avail()
+        .args(["family", "std", "--members", "a.b", "--owner", "alice"])
+        .output()
+        .expect("failed to execute");
+    // Reserved and invalid names block the family whoever the owner is
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("family std: blocked by std, std-a.b"),
+        "stderr: {stderr}"
+    );
+}
+
+#[test]
+fn suggest_without_valid_candidates_exits_1() {
+    let output = cargo_avail()
+        .args([
+            "suggest",
+            "std",
+            "--no-affixes",
+            "--no-truncations",
+            "--no-blends",
+        ])
+        .output()
+        .expect("failed to execute");
+    assert_eq!(output.status.code(), Some(1));
+    assert!(output.stdout.is_empty());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("no valid candidates"), "stderr: {stderr}");
+}
+
+#[test]
+fn suggest_requires_keywords_and_a_count() {
+    for args in [&["suggest"][..], &["suggest", "--count", "0", "json"]] {
+        let output = cargo_avail()
+            .args(args)
+            .output()
+            .expect("failed to execute");
+        assert_eq!(output.status.code(), Some(2), "args: {args:?}");
+    }
+}
+
+#[test]
+fn suggest_from_project_reports_what_it_found() {
+    let dir = std::env::temp_dir().join("cargo-avail-test-from-project");
+    std::fs::create_dir_all(&dir).expect("failed to create dir");
+    std::fs::write(
+        dir.join("Cargo.toml"),
+        "[package]\nname = \"x\"\ndescription = \"All about your usage\"\n",
+    )
+    .expect("failed to write");
+    std::fs::write(dir.join("README.md"), "# x\n\n## Installation\n").expect("failed to write");
+    let output = cargo_avail()
+        .arg("suggest")
+        .arg("--from-project")
+        .arg(&dir)
+        .output()
+        .expect("failed to execute");
+    assert_eq!(output.status.code(), Some(1));
+    assert!(output.stdout.is_empty());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("note: no keywords found in"),
+        "stderr: {stderr}"
+    );
+    assert!(stderr.contains("no valid candidates"), "stderr: {stderr}");
+}
+
+#[test]
+fn suggest_from_project_needs_a_manifest() {
+    let dir = std::env::temp_dir().join("cargo-avail-test-from-project-missing");
+    std::fs::create_dir_all(&dir).expect("failed to create dir");
+    let output = cargo_avail()
+        .arg("suggest")
+        .arg("--from-project")
+        .arg(&dir)
+        .output()
+        .expect("failed to execute");
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Cargo.toml"), "stderr: {stderr}");
+    // `--terms` only makes sense with a project
+    let output = cargo_avail()
+        .args(["suggest", "json", "--terms", "2"])
+        .output()
+        .expect("failed to execute");
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn cargo_plugin_names_report_command_clashes() {
+    // Reported without a lookup, so this holds even when the check fails
+    let output = cargo_avail()
+        .args(["--json", "cargo-build"])
+        .output()
+        .expect("failed to execute");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let parsed: serde_json::Value = serde_json::from_str(stdout.trim()).unwrap();
+    assert_eq!(parsed["subcommand_conflicts"][0]["kind"], "builtin");
+    assert_eq!(parsed["subcommand_conflicts"][0]["command"], "build");
+
+    let output = cargo_avail()
+        .args(["--json", "--no-subcommand-check", "cargo-build"])
+        .output()
+        .expect("failed to execute");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let parsed: serde_json::Value = serde_json::from_str(stdout.trim()).unwrap();
+    assert!(parsed.get("subcommand_conflicts").is_none(), "{parsed}");
+}
+
+#[test]
+fn bin_check_reports_bundled_tools_and_manifest_binaries() {
+    // With an empty PATH, only the bundled lists can match
+    let output = cargo_avail()
+        .args(["--json", "--bin-check", "rustfmt"])
+        .env("PATH", "")
+        .output()
+        .expect("failed to execute");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let parsed: serde_json::Value = serde_json::from_str(stdout.trim()).unwrap();
+    assert_eq!(parsed["binary_clashes"][0]["kind"], "rustup");
+
+    let dir = std::env::temp_dir().join("cargo-avail-test-bin-check");
+    std::fs::create_dir_all(&dir).expect("failed to create dir");
+    let manifest = dir.join("Cargo.toml");
+    std::fs::write(
+        &manifest,
+        "[package]\nname = \"acme-tools\"\n\n[[bin]]\nname = \"grep\"\n",
+    )
+    .expect("failed to write");
+    // No names given, so the package is checked, with its binaries
+    let output = cargo_avail()
+        .arg("--json")
+        .arg("--manifest-path")
+        .arg(&manifest)
+        .env("PATH", "")
+        .output()
+        .expect("failed to execute");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let parsed: serde_json::Value = serde_json::from_str(stdout.trim()).unwrap();
+    assert_eq!(parsed["name"], "acme-tools");
+    assert_eq!(parsed["binary_clashes"][0]["binary"], "grep");
+    assert_eq!(parsed["binary_clashes"][0]["kind"], "unix");
+}
+
+#[cfg(unix)]
+#[test]
+fn bin_check_skips_the_crates_own_installed_binary() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let home = std::env::temp_dir().join(format!(
+        "cargo-avail-test-bin-check-installed-{}",
+        std::process::id()
+    ));
+    let _ = std::fs::remove_dir_all(&home);
+    let bin = home.join("bin");
+    std::fs::create_dir_all(&bin).expect("failed to create dir");
+    let exe = bin.join("acmetool");
+    std::fs::write(&exe, "#!/bin/sh\n").expect("failed to write");
+    std::fs::set_permissions(&exe, std::fs::Permissions::from_mode(0o755))
+        .expect("failed to set permissions");
+    let clashes = |name: &str| {
+        let output = cargo_avail()
+            .args(["--json", "--bin-check", name])
+            .env("CARGO_HOME", &home)
+            .env_remove("CARGO_INSTALL_ROOT")
+            .env("PATH", &bin)
+            .output()
+            .expect("failed to execute");
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let parsed: serde_json::Value = serde_json::from_str(stdout.trim()).unwrap();
+        parsed["binary_clashes"].as_array().map_or(0, Vec::len)
+    };
+    assert_eq!(clashes("acmetool"), 1);
+    // Once `cargo install acmetool` is on record, the binary is its own
+    std::fs::write(
+        home.join(".crates2.json"),
+        r#"{"installs":{"acmetool 1.0.0 (registry+https://github.com/rust-lang/crates.io-index)":{"bins":["acmetool"]}}}"#,
+    )
+    .expect("failed to write");
+    assert_eq!(clashes("acmetool"), 0);
+    std::fs::remove_dir_all(&home).expect("failed to clean up");
+}
+
+#[test]
+fn bin_check_rejects_unreadable_manifests() {
+    let missing = std::env::temp_dir().join("cargo-avail-test-missing-Cargo.toml");
+    let output = cargo_avail()
+        .arg("--manifest-path")
+        .arg(&missing)
+        .output()
+        .expect("failed to execute");
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("reading"), "stderr: {stderr}");
+}
+
+#[test]
+fn distro_reports_packaging_names_and_existing_packages() {
+    let list = std::env::temp_dir().join("cargo-avail-test-distro-packages.txt");
+    std::fs::write(&list, "librust-acme-http-dev/stable 1.0.0-1 amd64\n").expect("failed to write");
+    // `std` is reserved, so this needs no network
+    let output = cargo_avail()
+        .args(["--json", "--distro", "debian,fedora", "Acme_HTTP", "std"])
+        .arg("--distro-packages")
+        .arg(&list)
+        .output()
+        .expect("failed to execute");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<serde_json::Value> = stdout
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(lines[0]["distro_packages"][0]["package"], "rust-acme-http");
+    assert_eq!(lines[0]["distro_packages"][1]["distro"], "fedora");
+    assert_eq!(lines[0]["distro_issues"][0]["distro"], "debian");
+    assert_eq!(lines[0]["distro_issues"][0]["kind"], "packaged");
+    assert_eq!(
+        lines[0]["distro_issues"][0]["package"],
+        "librust-acme-http-dev"
+    );
+    assert!(lines[1].get("distro_issues").is_none());
+
+    let output = cargo_avail()
+        .args(["--distro", "debian", "std-2"])
+        .output()
+        .expect("failed to execute");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("debian: `rust-std-2` reads as a version of `rust-std`"),
+        "stdout: {stdout}"
+    );
+}
+
+#[test]
+fn distro_rejects_unknown_distros_and_missing_lists() {
+    let missing = std::env::temp_dir().join("cargo-avail-test-distro-missing.txt");
+    let output = cargo_avail()
+        .args(["--distro", "debian", "std", "--distro-packages"])
+        .arg(&missing)
+        .output()
+        .expect("failed to execute");
+    assert_eq!(output.status.code(), Some(2));
+    let output = cargo_avail()
+        .args(["--distro", "arch", "std"])
+        .output()
+        .expect("failed to execute");
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn index_build_and_query() {
+    let dir = std::env::temp_dir().join("cargo-avail-test-index");
+    std::fs::create_dir_all(&dir).expect("failed to create dir");
+    let list = dir.join("names.txt");
+    let index = dir.join("names.idx");
+    std::fs::write(&list, "serde\nserde_json\ntokio\nacme-http\nacme_ws\n")
+        .expect("failed to write");
+    let output = cargo_avail()
+        .args(["index", "build"])
+        .arg(&list)
+        .arg("--output")
+        .arg(&index)
+        .output()
+        .expect("failed to execute");
+    assert_eq!(output.status.code(), Some(0));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("indexed 5 names"), "stderr: {stderr}");
+
+    let query = |args: &[&str]| {
+        let output = cargo_avail()
+            .args(["index", "query", "--index"])
+            .arg(&index)
+            .args(args)
+            .output()
+            .expect("failed to execute");
+        (
+            output.status.code(),
+            String::from_utf8_lossy(&output.stdout).into_owned(),
+        )
+    };
+    assert_eq!(
+        query(&["Serde-JSON", "acme-*"]),
+        (Some(0), "serde_json\nacme-http\nacme_ws\n".to_string())
+    );
+    assert_eq!(
+        query(&["--distance", "1", "tokyo"]),
+        (Some(0), "tokio\t1\n".to_string())
+    );
+    assert_eq!(query(&["hyper"]), (Some(1), String::new()));
+    // Prefix queries have no edit distance
+    assert_eq!(
+        query(&["--distance", "1", "acme-*"]),
+        (Some(2), String::new())
+    );
+}
+
+#[test]
+fn offline_checks_answer_from_the_index() {
+    let dir = std::env::temp_dir().join("cargo-avail-test-index-offline");
+    std::fs::create_dir_all(&dir).expect("failed to create dir");
+    let list = dir.join("names.txt");
+    let index = dir.join("names.idx");
+    std::fs::write(&list, "serde_json\n").expect("failed to write");
+    let output = cargo_avail()
+        .args(["index", "build", "--quiet"])
+        .arg(&list)
+        .arg("--output")
+        .arg(&index)
+        .output()
+        .expect("failed to execute");
+    assert_eq!(output.status.code(), Some(0));
+
+    let output = cargo_avail()
+        .arg("--index")
+        .arg(&index)
+        .args(["serde-json", "tovira-index"])
+        .output()
+        .expect("failed to execute");
+    assert_eq!(output.status.code(), Some(1));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("serde-json\ttaken (as serde_json)"),
+        "stdout: {stdout}"
+    );
+    assert!(
+        stdout.contains("tovira-index\tavailable"),
+        "stdout: {stdout}"
+    );
+}
+
+#[test]
+fn similarity_warnings_skip_near_misses_of_short_names() {
+    let dir = std::env::temp_dir().join("cargo-avail-test-similar");
+    std::fs::create_dir_all(&dir).expect("failed to create dir");
+    let list = dir.join("names.txt");
+    let index = dir.join("names.idx");
+    std::fs::write(&list, "rand\nbytes\nserde\n").expect("failed to write");
+    let output = cargo_avail()
+        .args(["index", "build", "--quiet"])
+        .arg(&list)
+        .arg("--output")
+        .arg(&index)
+        .output()
+        .expect("failed to execute");
+    assert_eq!(output.status.code(), Some(0));
+
+    let output = cargo_avail()
+        .arg("--index")
+        .arg(&index)
+        .args(["brand", "sand", "bytes2", "serdr"])
+        .output()
+        .expect("failed to execute");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines[0], "brand\tavailable");
+    assert_eq!(lines[1], "sand\tavailable");
+    assert_eq!(lines[2], "bytes2\tavailable");
+    assert_eq!(lines[3], "serdr\tavailable (similar to `serde`)");
+}
+
+#[test]
+fn missing_corrupt_or_unusable_indexes_are_errors() {
+    let corrupt = std::env::temp_dir().join("cargo-avail-test-index-corrupt.idx");
+    std::fs::write(&corrupt, "serde\n").expect("failed to write");
+    let missing = std::env::temp_dir().join("cargo-avail-test-index-missing.idx");
+    for path in [&corrupt, &missing] {
+        let output = cargo_avail()
+            .arg("--index")
+            .arg(path)
+            .arg("serde")
+            .output()
+            .expect("failed to execute");
+        assert_eq!(output.status.code(), Some(2), "path: {}", path.display());
+    }
+    // Options that need crates.io metadata are rejected up front
+    for flag in ["--crowded", "--rdeps", "--details", "--classify-taken"] {
+        let output = cargo_avail()
+            .args(["--offline", flag, "serde"])
+            .output()
+            .expect("failed to execute");
+        assert_eq!(output.status.code(), Some(2), "flag: {flag}");
+    }
+    let output = cargo_avail()
+        .args(["index", "query", "serde", "--index"])
+        .arg(&missing)
+        .output()
+        .expect("failed to execute");
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("index build"), "stderr: {stderr}");
+}
+
+#[test]
+fn expand_records_the_source_pattern() {
+    let output = cargo_avail()
+        .args([
+            "--expand",
+            "--json",
+            "std",
+            "{std,core}",
+            "compiler{-,_}builtins",
+        ])
+        .output()
+        .expect("failed to execute");
+    assert_eq!(output.status.code(), Some(1));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let parsed: Vec<serde_json::Value> = stdout
+        .lines()
+        .map(|l| serde_json::from_str(l).expect("each line should be valid JSON"))
+        .collect();
+    // `std` was given literally first, and the `_` spelling is a canonical duplicate
+    assert_eq!(parsed.len(), 3, "stdout: {stdout}");
+    assert!(parsed[0].get("pattern").is_none());
+    assert_eq!(parsed[1]["name"], "core");
+    assert_eq!(parsed[1]["pattern"], "{std,core}");
+    assert_eq!(parsed[2]["name"], "compiler-builtins");
+    assert_eq!(parsed[2]["pattern"], "compiler{-,_}builtins");
+}
+
+#[test]
+fn expand_applies_to_stdin() {
+    let output = Command::new(env!("CARGO_BIN_EXE_cargo-avail"))
+        .arg("--expand")
+        .stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .spawn()
+        .and_then(|mut child| {
+            use std::io::Write;
+            if let Some(ref mut stdin) = child.stdin {
+                stdin.write_all(b"{std,core,alloc}\n").ok();
+            }
+            child.wait_with_output()
+        })
+        .expect("failed to execute");
+    assert_eq!(output.status.code(), Some(1));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(stdout.lines().count(), 3, "stdout: {stdout}");
+}
+
+#[test]
+fn malformed_or_oversized_patterns_are_usage_errors() {
+    for args in [
+        &["--expand", "foo{a,b"][..],
+        &["--expand", "foo{bar}"],
+        &["--expand", "--max-expansion", "2", "foo{1..3}"],
+    ] {
+        let output = cargo_avail()
+            .args(args)
+            .output()
+            .expect("failed to execute");
+        assert_eq!(output.status.code(), Some(2), "args: {args:?}");
+        assert!(output.stdout.is_empty());
+    }
+}
+
+#[test]
+fn braces_are_literal_without_expand() {
+    let output = cargo_avail()
+        .args(["--json", "{std,core}"])
+        .output()
+        .expect("failed to execute");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let parsed: serde_json::Value = serde_json::from_str(stdout.trim()).unwrap();
+    assert_eq!(parsed["status"], "invalid");
+}
+
+#[test]
+fn combine_prunes_offline_before_checking() {
+    let dir = std::env::temp_dir().join("cargo-avail-test-combine");
+    std::fs::create_dir_all(&dir).expect("failed to create dir");
+    let first = dir.join("first.txt");
+    let second = dir.join("second.txt");
+    std::fs::write(&first, "# prefixes\ncompiler\n9lives\n").expect("failed to write");
+    std::fs::write(&second, "builtins\n").expect("failed to write");
+    // `compiler-builtins` is reserved and `9lives-builtins` starts with a digit
+    let output = cargo_avail()
+        .arg("combine")
+        .args([&first, &second])
+        .args(["--sample", "5", "--seed", "42"])
+        .output()
+        .expect("failed to execute");
+    assert_eq!(output.status.code(), Some(1));
+    assert!(output.stdout.is_empty());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("0 of 0 names available (2 combinations before pruning)"),
+        "stderr: {stderr}"
+    );
+}
+
+#[test]
+fn combine_stops_at_max_checks() {
+    let dir = std::env::temp_dir().join("cargo-avail-test-combine-max-checks");
+    std::fs::create_dir_all(&dir).expect("failed to create dir");
+    let words = dir.join("words.txt");
+    std::fs::write(&words, "alpha\nbeta\n").expect("failed to write");
+    let output = cargo_avail()
+        .arg("combine")
+        .args([&words, &words])
+        .args(["--max-checks", "0"])
+        .output()
+        .expect("failed to execute");
+    assert_eq!(output.status.code(), Some(1));
+    assert!(output.stdout.is_empty());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains(
+            "0 of 0 names available (4 combinations before pruning); stopped at --max-checks"
+        ),
+        "stderr: {stderr}"
+    );
+    // A sample is already bounded by its size
+    let output = cargo_avail()
+        .arg("combine")
+        .args([&words, &words])
+        .args(["--sample", "2", "--max-checks", "1"])
+        .output()
+        .expect("failed to execute");
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn combine_prints_like_a_plain_check() {
+    let dir = std::env::temp_dir().join("cargo-avail-test-combine-output");
+    std::fs::create_dir_all(&dir).expect("failed to create dir");
+    let first = dir.join("first.txt");
+    let second = dir.join("second.txt");
+    std::fs::write(&first, "cargo\n").expect("failed to write");
+    std::fs::write(&second, "build\n").expect("failed to write");
+    for format in [None, Some("--json")] {
+        let combined = cargo_avail()
+            .arg("combine")
+            .args([&first, &second])
+            .args(format)
+            .output()
+            .expect("failed to execute");
+        let plain = cargo_avail()
+            .arg("cargo-build")
+            .args(format)
+            .output()
+            .expect("failed to execute");
+        let stdout = String::from_utf8_lossy(&combined.stdout);
+        // The subcommand conflict needs no lookup, so it shows even offline
+        assert!(stdout.contains("built in"), "stdout: {stdout}");
+        assert_eq!(stdout, String::from_utf8_lossy(&plain.stdout));
+    }
+}
+
+#[test]
+fn combine_rejects_missing_or_empty_wordlists() {
+    let empty = std::env::temp_dir().join("cargo-avail-test-combine-empty.txt");
+    std::fs::write(&empty, "# nothing here\n\n").expect("failed to write");
+    let missing = std::env::temp_dir().join("cargo-avail-test-combine-missing.txt");
+    for path in [&empty, &missing] {
+        let output = cargo_avail()
+            .arg("combine")
+            .arg(path)
+            .output()
+            .expect("failed to execute");
+        assert_eq!(output.status.code(), Some(2), "path: {}", path.display());
+    }
+}
+
+#[test]
+fn random_rejects_bad_length_ranges() {
+    for len in ["9..3", "1..4", "short"] {
+        let output = cargo_avail()
+            .args(["random", "--len", len])
+            .output()
+            .expect("failed to execute");
+        assert_eq!(output.status.code(), Some(2), "len: {len}");
+    }
+}
+
+#[test]
+fn random_stops_when_no_names_fit() {
+    // Nothing in the bundled corpus leads to names this long, so nothing is
+    // checked and the exit code says no names were found
+    let output = cargo_avail()
+        .args(["random", "--len", "60..64", "--seed", "1"])
+        .output()
+        .expect("failed to execute");
+    assert_eq!(output.status.code(), Some(1));
+    assert!(output.stdout.is_empty());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("found 0 of 10 available names in 0 candidates"),
+        "stderr: {stderr}"
+    );
+    assert!(!stderr.contains("note: seed"), "stderr: {stderr}");
+}
+
+#[test]
+fn unknown_sort_order_is_a_usage_error() {
+    let output = cargo_avail()
+        .args(["--sort", "length", "std"])
+        .output()
+        .expect("failed to execute");
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn top_only_prints_available_names() {
+    let output = cargo_avail()
+        .args(["--top", "5", "std", "core", "a.b"])
+        .output()
+        .expect("failed to execute");
+    // Nothing is available, so nothing is printed, but the exit code still
+    // covers every name
+    assert_eq!(output.status.code(), Some(1));
+    assert!(output.stdout.is_empty());
+}
+
+#[test]
+fn unavailable_names_are_not_scored() {
+    let output = cargo_avail()
+        .args(["--json", "--sort", "score", "std"])
+        .output()
+        .expect("failed to execute");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let parsed: serde_json::Value = serde_json::from_str(stdout.trim()).unwrap();
+    assert!(parsed.get("score").is_none());
+}